@@ -1,8 +1,17 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+use std::str::FromStr;
 
 use dprint_core::configuration::*;
+#[cfg(feature = "wasm")]
+use dprint_core::plugins::{ConfigChange, ConfigChangeKind};
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 
+mod schema;
+
+pub use schema::json_schema;
+
 #[derive(Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Configuration {
@@ -12,8 +21,15 @@ pub struct Configuration {
     pub new_line_kind: NewLineKind,
 
     pub classname_attributes: HashSet<String>,
+    // glob and regex entries of `classname_attributes`, compiled
+    #[serde(skip)]
+    pub attribute_patterns: Vec<(String, Regex)>,
+    // wrapping options of a single attribute, keyed by its name
+    pub attribute_overrides: HashMap<String, AttributeOverrides>,
+    pub classname_functions: HashSet<String>,
 
     pub enable_wrap: bool,
+    pub sort_classes: bool,
     // ignore when `enable_wrap` is false
     pub allow_line_overflow: bool,
     // ignore when `enable_wrap` is false
@@ -26,36 +42,76 @@ pub struct Configuration {
     pub line_width: u32,
 }
 
+/// Options of one `classnameAttributes` entry, `None` falls back to the top-level value
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AttributeOverrides {
+    pub enable_wrap: Option<bool>,
+    pub line_width: Option<u32>,
+    pub indent_to_quote: Option<bool>,
+    pub allow_line_overflow: Option<bool>,
+}
+
 impl Default for Configuration {
     fn default() -> Self {
         Self {
             use_tabs: false,
-            new_line_kind: NewLineKind::Auto,
+            new_line_kind: NewLineKind::LineFeed,
             classname_attributes: HashSet::from_iter(vec![
                 String::from("class"),
                 String::from("className"),
             ]),
+            attribute_patterns: Vec::new(),
+            attribute_overrides: HashMap::new(),
+            classname_functions: HashSet::from_iter(vec![
+                String::from("classnames"),
+                String::from("clsx"),
+                String::from("cn"),
+                String::from("cva"),
+                String::from("twMerge"),
+            ]),
             enable_wrap: true,
+            sort_classes: false,
             allow_line_overflow: false,
             indent_to_quote: true,
             indent_width: 2,
-            line_width_includes_indent: false,
+            line_width_includes_indent: true,
             line_width: 120,
         }
     }
 }
 
 impl Configuration {
+    /// Invalid patterns never match
     pub fn with_classname_attributes(mut self, patterns: HashSet<String>) -> Self {
+        self.attribute_patterns = compile_attribute_patterns(&patterns);
         self.classname_attributes = patterns;
         self
     }
 
+    /// Add `name` to the class attributes with its own wrapping options
+    pub fn with_attribute_overrides(mut self, name: &str, overrides: AttributeOverrides) -> Self {
+        self.classname_attributes.insert(name.to_string());
+        self.attribute_patterns = compile_attribute_patterns(&self.classname_attributes);
+        self.attribute_overrides.insert(name.to_string(), overrides);
+        self
+    }
+
+    pub fn with_classname_functions(mut self, names: HashSet<String>) -> Self {
+        self.classname_functions = names;
+        self
+    }
+
     pub fn with_enable_wrap(mut self, enabled: bool) -> Self {
         self.enable_wrap = enabled;
         self
     }
 
+    pub fn with_sort_classes(mut self, enabled: bool) -> Self {
+        self.sort_classes = enabled;
+        self
+    }
+
     pub fn with_allow_line_overflow(mut self, enabled: bool) -> Self {
         self.allow_line_overflow = enabled;
         self
@@ -82,6 +138,43 @@ impl Configuration {
     }
 }
 
+impl Configuration {
+    /// The `classnameAttributes` entry matching the attribute `name`
+    pub fn find_classname_attribute(&self, name: &str) -> Option<&str> {
+        self.classname_attributes
+            .get(name)
+            .or_else(|| {
+                self.attribute_patterns
+                    .iter()
+                    .find(|(_, regex)| regex.is_match(name))
+                    .map(|(pattern, _)| pattern)
+            })
+            .map(String::as_str)
+    }
+}
+
+const PROPERTY_NAMES: &[&str] = &[
+    "useTabs",
+    "newLineKind",
+    "classnameAttributes",
+    "classnameFunctions",
+    "enableWrap",
+    "sortClasses",
+    "allowLineOverflow",
+    "indentToQuote",
+    "indentWidth",
+    "lineWidthIncludesIndent",
+    "lineWidth",
+];
+
+const ATTRIBUTE_PROPERTY_NAMES: &[&str] = &[
+    "name",
+    "enableWrap",
+    "lineWidth",
+    "indentToQuote",
+    "allowLineOverflow",
+];
+
 impl Configuration {
     pub fn resolve_config(
         config: ConfigKeyMap,
@@ -89,15 +182,25 @@ impl Configuration {
     ) -> ResolveConfigurationResult<Self> {
         let mut config = config;
         let mut diagnostics = Vec::new();
+        take_single_names(&mut config, &mut diagnostics);
+
+        let (classname_attributes, attribute_overrides) =
+            match get_classname_attributes(&mut config, &mut diagnostics) {
+                Some(values) => values,
+                None => (
+                    HashSet::from_iter(vec![String::from("class"), String::from("className")]),
+                    HashMap::new(),
+                ),
+            };
 
-        let classname_attributes = match get_nullable_vec(
+        let classname_functions = match get_nullable_vec(
             &mut config,
-            "classnameAttributes",
+            "classnameFunctions",
             |value, i, diagnostics| match value {
                 ConfigKeyValue::String(value) => Some(value),
                 _ => {
                     diagnostics.push(ConfigurationDiagnostic {
-                        property_name: format!("classnameAttributes[{}]", i),
+                        property_name: format!("classnameFunctions[{}]", i),
                         message: String::from("Expected array of strings"),
                     });
                     None
@@ -106,18 +209,38 @@ impl Configuration {
             &mut diagnostics,
         ) {
             Some(values) => HashSet::from_iter(values),
-            None => HashSet::from_iter(vec![String::from("class"), String::from("className")]),
+            None => HashSet::from_iter(vec![
+                String::from("classnames"),
+                String::from("clsx"),
+                String::from("cn"),
+                String::from("cva"),
+                String::from("twMerge"),
+            ]),
         };
 
         let resolved = Self {
-            use_tabs: global_config
-                .use_tabs
-                .unwrap_or(RECOMMENDED_GLOBAL_CONFIGURATION.use_tabs),
-            new_line_kind: global_config
-                .new_line_kind
-                .unwrap_or(RECOMMENDED_GLOBAL_CONFIGURATION.new_line_kind),
+            use_tabs: get_value(
+                &mut config,
+                "useTabs",
+                global_config
+                    .use_tabs
+                    .unwrap_or(RECOMMENDED_GLOBAL_CONFIGURATION.use_tabs),
+                &mut diagnostics,
+            ),
+            new_line_kind: get_value(
+                &mut config,
+                "newLineKind",
+                global_config
+                    .new_line_kind
+                    .unwrap_or(RECOMMENDED_GLOBAL_CONFIGURATION.new_line_kind),
+                &mut diagnostics,
+            ),
+            attribute_patterns: compile_attribute_patterns(&classname_attributes),
             classname_attributes,
+            attribute_overrides,
+            classname_functions,
             enable_wrap: get_value(&mut config, "enableWrap", true, &mut diagnostics),
+            sort_classes: get_value(&mut config, "sortClasses", false, &mut diagnostics),
             allow_line_overflow: get_value(
                 &mut config,
                 "allowLineOverflow",
@@ -125,12 +248,14 @@ impl Configuration {
                 &mut diagnostics,
             ),
             indent_to_quote: get_value(&mut config, "indentToQuote", true, &mut diagnostics),
-            indent_width: get_value(
+            indent_width: get_minimum_value(
                 &mut config,
                 "indentWidth",
                 global_config
                     .indent_width
                     .unwrap_or(RECOMMENDED_GLOBAL_CONFIGURATION.indent_width),
+                RECOMMENDED_GLOBAL_CONFIGURATION.indent_width,
+                1,
                 &mut diagnostics,
             ),
             line_width_includes_indent: get_value(
@@ -139,15 +264,18 @@ impl Configuration {
                 true,
                 &mut diagnostics,
             ),
-            line_width: get_value(
+            line_width: get_minimum_value(
                 &mut config,
                 "lineWidth",
                 global_config
                     .line_width
                     .unwrap_or(RECOMMENDED_GLOBAL_CONFIGURATION.line_width),
+                RECOMMENDED_GLOBAL_CONFIGURATION.line_width,
+                1,
                 &mut diagnostics,
             ),
         };
+        diagnostics.extend(get_unknown_diagnostics(config, PROPERTY_NAMES));
 
         ResolveConfigurationResult {
             diagnostics,
@@ -155,3 +283,311 @@ impl Configuration {
         }
     }
 }
+
+/// Options taking a list, where a single name is migrated by `dprint config update`
+const LIST_PROPERTY_NAMES: &[&str] = &["classnameAttributes", "classnameFunctions"];
+
+/// Changes for `dprint config update` that rewrite outdated values into the
+/// current shape: a single name given for a list option becomes a list. No
+/// option has been renamed or removed yet, so there are no key migrations.
+#[cfg(feature = "wasm")]
+pub fn check_config_updates(config: &ConfigKeyMap) -> Vec<ConfigChange> {
+    LIST_PROPERTY_NAMES
+        .iter()
+        .filter_map(|key| match config.get(*key)? {
+            ConfigKeyValue::String(value) => Some(ConfigChange {
+                path: vec![key.to_string().into()],
+                kind: ConfigChangeKind::Set(ConfigKeyValue::Array(vec![ConfigKeyValue::String(
+                    value.clone(),
+                )])),
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+/// A single name where a list is expected is reported and dropped, pointing
+/// to the migration of `dprint config update`
+fn take_single_names(config: &mut ConfigKeyMap, diagnostics: &mut Vec<ConfigurationDiagnostic>) {
+    for key in LIST_PROPERTY_NAMES {
+        if let Some(ConfigKeyValue::String(_)) = config.get(*key) {
+            config.shift_remove(*key);
+            diagnostics.push(ConfigurationDiagnostic {
+                property_name: key.to_string(),
+                message: String::from(
+                    "Expected an array, run `dprint config update` to convert a single name.",
+                ),
+            });
+        }
+    }
+}
+
+/// `classnameAttributes` entries are names or objects with a `name` and its own options
+fn get_classname_attributes(
+    config: &mut ConfigKeyMap,
+    diagnostics: &mut Vec<ConfigurationDiagnostic>,
+) -> Option<(HashSet<String>, HashMap<String, AttributeOverrides>)> {
+    let values = match config.shift_remove("classnameAttributes")? {
+        ConfigKeyValue::Array(values) => values,
+        _ => {
+            diagnostics.push(ConfigurationDiagnostic {
+                property_name: String::from("classnameAttributes"),
+                message: String::from("Expected an array."),
+            });
+            return None;
+        }
+    };
+
+    let mut names = HashSet::new();
+    let mut overrides = HashMap::new();
+    for (i, value) in values.into_iter().enumerate() {
+        let property_name = format!("classnameAttributes[{}]", i);
+        let (name, entry_overrides) = match value {
+            ConfigKeyValue::String(name) => (name, None),
+            ConfigKeyValue::Object(entry) => {
+                match get_attribute_overrides(entry, &property_name, diagnostics) {
+                    Some((name, entry_overrides)) => (name, Some(entry_overrides)),
+                    None => continue,
+                }
+            }
+            _ => {
+                diagnostics.push(ConfigurationDiagnostic {
+                    property_name,
+                    message: String::from("Expected a string or an object"),
+                });
+                continue;
+            }
+        };
+
+        if let Some(Err(err)) = parse_attribute_pattern(&name) {
+            diagnostics.push(ConfigurationDiagnostic {
+                property_name,
+                message: format!("Invalid pattern `{name}`: {err}"),
+            });
+            continue;
+        }
+
+        if let Some(entry_overrides) = entry_overrides {
+            overrides.insert(name.clone(), entry_overrides);
+        }
+        names.insert(name);
+    }
+
+    Some((names, overrides))
+}
+
+fn get_attribute_overrides(
+    mut entry: ConfigKeyMap,
+    property_name: &str,
+    diagnostics: &mut Vec<ConfigurationDiagnostic>,
+) -> Option<(String, AttributeOverrides)> {
+    let name = match entry.shift_remove("name") {
+        Some(ConfigKeyValue::String(name)) => Some(name),
+        _ => {
+            diagnostics.push(ConfigurationDiagnostic {
+                property_name: format!("{property_name}.name"),
+                message: String::from("Expected a string"),
+            });
+            None
+        }
+    };
+
+    let mut entry_diagnostics = Vec::new();
+    let overrides = AttributeOverrides {
+        enable_wrap: get_nullable_value(&mut entry, "enableWrap", &mut entry_diagnostics),
+        line_width: get_nullable_value(&mut entry, "lineWidth", &mut entry_diagnostics)
+            .and_then(|width| check_minimum("lineWidth", width, 1, &mut entry_diagnostics)),
+        indent_to_quote: get_nullable_value(&mut entry, "indentToQuote", &mut entry_diagnostics),
+        allow_line_overflow: get_nullable_value(
+            &mut entry,
+            "allowLineOverflow",
+            &mut entry_diagnostics,
+        ),
+    };
+    entry_diagnostics.extend(get_unknown_diagnostics(entry, ATTRIBUTE_PROPERTY_NAMES));
+
+    diagnostics.extend(
+        entry_diagnostics
+            .into_iter()
+            .map(|diagnostic| ConfigurationDiagnostic {
+                property_name: format!("{property_name}.{}", diagnostic.property_name),
+                message: diagnostic.message,
+            }),
+    );
+
+    Some((name?, overrides))
+}
+
+/// `get_value` for a number with a lower bound. A value below `min`, including
+/// one inherited from the global configuration, is reported and replaced by
+/// `fallback`.
+fn get_minimum_value<T>(
+    config: &mut ConfigKeyMap,
+    key: &str,
+    default: T,
+    fallback: T,
+    min: T,
+    diagnostics: &mut Vec<ConfigurationDiagnostic>,
+) -> T
+where
+    T: FromStr + PartialOrd + Display + Copy,
+    <T as FromStr>::Err: Display,
+{
+    let value = get_value(config, key, default, diagnostics);
+    check_minimum(key, value, min, diagnostics).unwrap_or(fallback)
+}
+
+/// `None` and a diagnostic when `value` is below `min`
+fn check_minimum<T: PartialOrd + Display>(
+    key: &str,
+    value: T,
+    min: T,
+    diagnostics: &mut Vec<ConfigurationDiagnostic>,
+) -> Option<T> {
+    if value < min {
+        diagnostics.push(ConfigurationDiagnostic {
+            property_name: key.to_string(),
+            message: format!("Expected a number of at least {min}, got {value}"),
+        });
+        return None;
+    }
+    Some(value)
+}
+
+/// `get_unknown_property_diagnostics`, suggesting the known name closest to a typo
+fn get_unknown_diagnostics(
+    config: ConfigKeyMap,
+    known_names: &[&str],
+) -> Vec<ConfigurationDiagnostic> {
+    get_unknown_property_diagnostics(config)
+        .into_iter()
+        .map(|mut diagnostic| {
+            if let Some(name) = closest_name(&diagnostic.property_name, known_names) {
+                diagnostic.message = format!("{}, did you mean `{name}`?", diagnostic.message);
+            }
+            diagnostic
+        })
+        .collect()
+}
+
+/// Case-insensitive edit distance, up to a third of the name's length
+fn closest_name<'a>(name: &str, known_names: &[&'a str]) -> Option<&'a str> {
+    let name: Vec<char> = name.to_lowercase().chars().collect();
+    known_names
+        .iter()
+        .map(|known| {
+            let known_chars: Vec<char> = known.to_lowercase().chars().collect();
+            (edit_distance(&name, &known_chars), *known)
+        })
+        .filter(|(distance, known)| *distance <= (known.len() / 3).max(1))
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, known)| known)
+}
+
+/// Levenshtein distance, counting a swap of adjacent characters as one edit
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let mut rows = vec![(0..=b.len()).collect::<Vec<_>>()];
+    for i in 1..=a.len() {
+        let mut row = vec![i; b.len() + 1];
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            row[j] = (rows[i - 1][j] + 1)
+                .min(row[j - 1] + 1)
+                .min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                row[j] = row[j].min(rows[i - 2][j - 2] + 1);
+            }
+        }
+        rows.push(row);
+    }
+    rows[a.len()][b.len()]
+}
+
+/// `None` for a plain attribute name. `/.../` is a regex, `*` and `?` make a
+/// glob matching the whole name.
+fn parse_attribute_pattern(entry: &str) -> Option<Result<Regex, regex::Error>> {
+    if let Some(pattern) = entry
+        .strip_prefix('/')
+        .and_then(|entry| entry.strip_suffix('/'))
+    {
+        return Some(Regex::new(pattern));
+    }
+
+    if !entry.contains(['*', '?']) {
+        return None;
+    }
+
+    let pattern = entry
+        .split_inclusive(['*', '?'])
+        .map(|segment| match segment.strip_suffix(['*', '?']) {
+            Some(literal) if segment.ends_with('*') => format!("{}.*", regex::escape(literal)),
+            Some(literal) => format!("{}.", regex::escape(literal)),
+            None => regex::escape(segment),
+        })
+        .collect::<String>();
+    Some(Regex::new(&format!("^{pattern}$")))
+}
+
+fn compile_attribute_patterns(entries: &HashSet<String>) -> Vec<(String, Regex)> {
+    let mut patterns: Vec<_> = entries
+        .iter()
+        .filter_map(|entry| match parse_attribute_pattern(entry)? {
+            Ok(regex) => Some((entry.clone(), regex)),
+            Err(_) => None,
+        })
+        .collect();
+    // a name matching several patterns always picks the same entry
+    patterns.sort_by(|a, b| a.0.cmp(&b.0));
+    patterns
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config_map(json: &str) -> ConfigKeyMap {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn points_single_names_to_config_update() {
+        let config = config_map(r#"{"classnameAttributes": "tw", "classnameFunctions": "cn"}"#);
+        let result = Configuration::resolve_config(config, &GlobalConfiguration::default());
+
+        let messages: Vec<_> = result
+            .diagnostics
+            .iter()
+            .map(|diagnostic| {
+                (
+                    diagnostic.property_name.as_str(),
+                    diagnostic.message.as_str(),
+                )
+            })
+            .collect();
+        let message = "Expected an array, run `dprint config update` to convert a single name.";
+        assert_eq!(
+            messages,
+            [
+                ("classnameAttributes", message),
+                ("classnameFunctions", message)
+            ]
+        );
+    }
+
+    #[cfg(feature = "wasm")]
+    #[test]
+    fn converts_single_names_into_lists() {
+        let config = config_map(
+            r#"{"classnameAttributes": "tw", "classnameFunctions": ["cn"], "lineWidth": 80}"#,
+        );
+        let changes = serde_json::to_value(check_config_updates(&config)).unwrap();
+
+        assert_eq!(
+            changes,
+            serde_json::json!([
+                { "path": ["classnameAttributes"], "kind": "Set", "value": ["tw"] }
+            ])
+        );
+        assert!(check_config_updates(&config_map(r#"{"classnameFunctions": ["cn"]}"#)).is_empty());
+    }
+}
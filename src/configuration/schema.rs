@@ -0,0 +1,103 @@
+use serde_json::{Value, json};
+
+/// JSON schema of the `classnameWrap` block of a dprint configuration file,
+/// published next to the wasm file of every release
+pub fn json_schema() -> Value {
+    json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "$id": format!(
+            "https://plugins.dprint.dev/liuhq/dprint-plugin-classname-wrap/{}/schema.json",
+            env!("CARGO_PKG_VERSION")
+        ),
+        "title": "dprint-plugin-classname-wrap configuration",
+        "type": "object",
+        "definitions": {
+            "enableWrap": {
+                "description": "Enable line wrapping, when `false` class lists are collapsed onto a single line.",
+                "type": "boolean",
+                "default": true
+            },
+            "allowLineOverflow": {
+                "description": "Allow the last class to exceed `lineWidth`.",
+                "type": "boolean",
+                "default": false
+            },
+            "indentToQuote": {
+                "description": "Indent lines aligned to the quote (`true`) or normally (`false`).",
+                "type": "boolean",
+                "default": true
+            },
+            "lineWidth": {
+                "description": "Maximum line width.",
+                "type": "integer",
+                "minimum": 1,
+                "default": 120
+            }
+        },
+        "properties": {
+            "useTabs": {
+                "description": "Indent continuation lines with tabs.",
+                "type": "boolean",
+                "default": false
+            },
+            "newLineKind": {
+                "description": "Line ending of inserted line breaks.",
+                "type": "string",
+                "enum": ["auto", "crlf", "lf"],
+                "default": "lf"
+            },
+            "classnameAttributes": {
+                "description": "Attributes to format: names, globs (`*` and `?`), regexes between slashes, or objects with their own wrapping options.",
+                "type": "array",
+                "items": {
+                    "oneOf": [
+                        { "type": "string" },
+                        {
+                            "type": "object",
+                            "properties": {
+                                "name": {
+                                    "description": "Attribute name, glob or regex.",
+                                    "type": "string"
+                                },
+                                "enableWrap": { "$ref": "#/definitions/enableWrap" },
+                                "lineWidth": { "$ref": "#/definitions/lineWidth" },
+                                "indentToQuote": { "$ref": "#/definitions/indentToQuote" },
+                                "allowLineOverflow": { "$ref": "#/definitions/allowLineOverflow" }
+                            },
+                            "required": ["name"],
+                            "additionalProperties": false
+                        }
+                    ]
+                },
+                "default": ["class", "className"]
+            },
+            "classnameFunctions": {
+                "description": "Functions whose string arguments are formatted.",
+                "type": "array",
+                "items": { "type": "string" },
+                "default": ["classnames", "clsx", "cn", "cva", "twMerge"]
+            },
+            "enableWrap": { "$ref": "#/definitions/enableWrap" },
+            "sortClasses": {
+                "description": "Sort classes in Tailwind CSS order before wrapping.",
+                "type": "boolean",
+                "default": false
+            },
+            "allowLineOverflow": { "$ref": "#/definitions/allowLineOverflow" },
+            "indentToQuote": { "$ref": "#/definitions/indentToQuote" },
+            "indentWidth": {
+                "description": "Number of spaces per indent.",
+                "type": "integer",
+                "minimum": 1,
+                "maximum": 255,
+                "default": 2
+            },
+            "lineWidthIncludesIndent": {
+                "description": "Include indentation in `lineWidth` calculation.",
+                "type": "boolean",
+                "default": true
+            },
+            "lineWidth": { "$ref": "#/definitions/lineWidth" }
+        }
+    })
+}
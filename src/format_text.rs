@@ -1,32 +1,89 @@
+use std::ops::Range;
 use std::path::Path;
 
 use dprint_core::configuration::resolve_new_line_kind;
 use dprint_core::formatting::*;
 
 use super::configuration::Configuration;
-use super::generation::generate;
+use super::generation::{check_file_type, generate};
+
+const BOM: char = '\u{FEFF}';
 
 pub struct FormatTextOptions<'a> {
     pub path: &'a Path,
     pub extension: Option<&'a str>,
     pub text: String,
     pub config: &'a Configuration,
+    /// Byte range of `text` to format, class values outside of it are kept as is
+    pub range: Option<Range<usize>>,
 }
 
 pub fn format_text(options: FormatTextOptions) -> anyhow::Result<Option<String>> {
-    let result = format_text_inner(options.path, &options.text, options.config)?;
-    Ok(Some(result))
+    // unsupported files are an error even when the pre-scan would skip them
+    check_file_type(options.path)?;
+    if !may_contain_classes(&options.text, options.config) {
+        return Ok(None);
+    }
+
+    let mut result = format_text_inner(options.path, &options.text, options.config, options.range)?;
+    // the BOM is stripped before generating, put it back
+    if options.text.starts_with(BOM) {
+        result.insert(0, BOM);
+    }
+
+    if result == options.text {
+        Ok(None)
+    } else {
+        Ok(Some(result))
+    }
+}
+
+/// Cheap pre-scan, a file mentioning none of the configured attribute or
+/// function names has nothing to format. Attribute patterns are not scanned.
+fn may_contain_classes(text: &str, config: &Configuration) -> bool {
+    !config.attribute_patterns.is_empty()
+        || config
+            .classname_attributes
+            .iter()
+            .chain(&config.classname_functions)
+            .any(|name| text.contains(name.as_str()))
 }
 
 fn format_text_inner(
     path: &Path,
     source_text: &str,
     config: &Configuration,
+    range: Option<Range<usize>>,
+) -> anyhow::Result<String> {
+    // the range is a position in the text with its BOM
+    let bom_len = source_text.len() - strip_bom(source_text).len();
+    let range =
+        range.map(|range| range.start.saturating_sub(bom_len)..range.end.saturating_sub(bom_len));
+    let result = format_once(path, source_text, config, range.as_ref())?;
+
+    // formatting must be idempotent, check it while developing. The range
+    // does not match the formatted text anymore, so only whole files are checked.
+    #[cfg(debug_assertions)]
+    if range.is_none() && format_once(path, &result, config, None)? != result {
+        anyhow::bail!(
+            "Formatting {} is not stable, a second pass changed the output",
+            path.display()
+        );
+    }
+
+    Ok(result)
+}
+
+fn format_once(
+    path: &Path,
+    source_text: &str,
+    config: &Configuration,
+    range: Option<&Range<usize>>,
 ) -> anyhow::Result<String> {
     let mut maybe_err: Box<Option<anyhow::Error>> = Box::new(None);
     let strip_bom_text = strip_bom(source_text);
     let result = dprint_core::formatting::format(
-        || match generate(path, strip_bom_text, config) {
+        || match generate(path, strip_bom_text, config, range) {
             Ok(print_items) => print_items,
             Err(err) => {
                 maybe_err.replace(err);
@@ -44,7 +101,7 @@ fn format_text_inner(
 }
 
 fn strip_bom(text: &str) -> &str {
-    text.strip_prefix("\u{FEFF}").unwrap_or(text)
+    text.strip_prefix(BOM).unwrap_or(text)
 }
 
 fn config_to_print_options(file_text: &str, config: &Configuration) -> PrintOptions {
@@ -55,3 +112,71 @@ fn config_to_print_options(file_text: &str, config: &Configuration) -> PrintOpti
         new_line_text: resolve_new_line_kind(file_text, config.new_line_kind),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(path: &str, text: &str) -> anyhow::Result<Option<String>> {
+        format_text(FormatTextOptions {
+            path: Path::new(path),
+            extension: None,
+            text: text.to_string(),
+            config: &Configuration::default(),
+            range: None,
+        })
+    }
+
+    fn format_range(text: &str, range: Range<usize>) -> String {
+        let config = Configuration::default().with_line_width(20);
+        format_text(FormatTextOptions {
+            path: Path::new("file.tsx"),
+            extension: None,
+            text: text.to_string(),
+            config: &config,
+            range: Some(range),
+        })
+        .unwrap()
+        .unwrap_or_else(|| text.to_string())
+    }
+
+    #[test]
+    fn errors_on_unsupported_files_without_classes() {
+        assert!(format("file.md", "x").is_err());
+        assert!(format("file.md", "className").is_err());
+        assert_eq!(format("file.tsx", "x").unwrap(), None);
+    }
+
+    #[test]
+    fn keeps_the_bom() {
+        let classes = ["px-2 py-4 text-sm font-bold text-ctp-text"; 4].join(" ");
+        let text = format!("\u{FEFF}<div className=\"{classes}\" />;\n");
+        let formatted = format("file.tsx", &text).unwrap().unwrap();
+        assert!(formatted.starts_with("\u{FEFF}<div className=\"px-2"));
+        assert_ne!(formatted, text);
+
+        // unchanged apart from the BOM, which is not a change
+        let text = "\u{FEFF}<div className=\"px-2 py-4\" />;\n";
+        assert_eq!(format("file.tsx", text).unwrap(), None);
+    }
+
+    #[test]
+    fn formats_values_overlapping_the_range() {
+        let text = "const a = <div className=\"px-2 py-4 text-sm\" />;\n\
+                    const b = <div className=\"px-2 py-4 text-sm\" />;\n";
+        let first_end = text.find(" />").unwrap();
+        let second_start = text.rfind("\"px-2").unwrap();
+        let (first, second) = text.split_at(text.find("const b").unwrap());
+
+        let formatted = format_range(text, second_start + 1..second_start + 5);
+        assert!(formatted.starts_with(first));
+        assert_ne!(&formatted[first.len()..], second);
+
+        let formatted = format_range(text, second_start..second_start);
+        assert!(formatted.starts_with(first));
+        assert_ne!(&formatted[first.len()..], second);
+
+        // touching a value is not overlapping it
+        assert_eq!(format_range(text, first_end..second_start), text);
+    }
+}
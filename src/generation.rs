@@ -1,6 +1,14 @@
+mod astro;
+mod error;
 mod generate;
+mod html;
+mod markup;
+mod sorter;
+mod svelte;
 mod types;
 mod visitor;
+mod vue;
 mod wrapper;
 
-pub use generate::generate;
+pub use error::{ParseDiagnostic, ParseError};
+pub use generate::{check_file_type, generate};
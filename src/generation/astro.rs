@@ -0,0 +1,129 @@
+use dprint_core::formatting::PrintItems;
+use oxc::{
+    allocator::Allocator,
+    ast::ast::Statement,
+    ast_visit::Visit,
+    parser::Parser,
+    span::{SourceType, Span},
+};
+use std::ops::Range;
+
+use crate::{
+    configuration::Configuration,
+    generation::{
+        error::ParseError,
+        generate::create_visitor,
+        markup::{
+            MarkupAttribute, MarkupTokenizer, MarkupValueKind, MaskedSource, StatementCursor,
+        },
+        types::IntoU32,
+        visitor::Visitor,
+    },
+};
+
+const FENCE: &str = "---";
+
+pub fn generate(
+    source_text: &str,
+    config: &Configuration,
+    range: Option<&Range<usize>>,
+) -> anyhow::Result<PrintItems> {
+    let frontmatter = Frontmatter::parse(source_text);
+    let template_start = frontmatter.as_ref().map_or(0, |f| f.end);
+    let elements: Vec<_> = MarkupTokenizer::new(source_text)
+        .with_start(template_start)
+        .collect();
+    let mut visitor = create_visitor(source_text, config, range);
+
+    // frontmatter and `class:list={...}` expressions are parsed together in one program
+    let mut masked = MaskedSource::new(source_text);
+    if let Some(frontmatter) = &frontmatter {
+        masked.keep(frontmatter.content);
+    }
+    for element in &elements {
+        for attribute in &element.attributes {
+            if let Some((_, value)) = class_expression_value(source_text, &visitor, attribute) {
+                masked.keep_expression(value);
+            }
+        }
+    }
+
+    let masked_text = masked.into_string();
+    let allocator = Allocator::default();
+    let parsed = Parser::new(&allocator, &masked_text, SourceType::ts()).parse();
+    if parsed.panicked || !parsed.errors.is_empty() {
+        return Err(ParseError::from_oxc(source_text, &parsed.errors, parsed.panicked).into());
+    }
+
+    let mut statements = StatementCursor::new(&parsed.program);
+    if let Some(frontmatter) = &frontmatter {
+        for statement in statements.take_within(frontmatter.content) {
+            visitor.visit_statement(statement);
+        }
+    }
+
+    for element in &elements {
+        visitor.enter_markup_element(element);
+
+        for attribute in &element.attributes {
+            match class_expression_value(source_text, &visitor, attribute) {
+                Some((name, value)) => {
+                    for statement in statements.take_within(value) {
+                        if let Statement::ExpressionStatement(statement) = statement {
+                            visitor.visit_class_expression(
+                                &statement.expression,
+                                name,
+                                &attribute.name,
+                            );
+                        }
+                    }
+                }
+                None => visitor.visit_markup_attribute(attribute),
+            }
+        }
+    }
+
+    Ok(visitor.finish())
+}
+
+/// The `---` fenced script at the top of the component
+struct Frontmatter {
+    content: Span,
+    /// End of the closing fence, where the template starts
+    end: usize,
+}
+
+impl Frontmatter {
+    fn parse(source_text: &str) -> Option<Self> {
+        let open = source_text.len() - source_text.trim_start().len();
+        let after_open = source_text[open..].strip_prefix(FENCE)?;
+        let content_start = source_text.len() - after_open.len();
+
+        let close = after_open
+            .match_indices(FENCE)
+            .map(|(i, _)| content_start + i)
+            .find(|i| source_text[..*i].ends_with('\n'))?;
+
+        Some(Self {
+            content: Span::new(content_start.into_u32(), close.into_u32()),
+            end: close + FENCE.len(),
+        })
+    }
+}
+
+/// Class attribute name and value span of `class:list={...}` or `class={...}`
+/// when `class` is configured
+fn class_expression_value<'s>(
+    source_text: &'s str,
+    visitor: &Visitor<'_>,
+    attribute: &MarkupAttribute,
+) -> Option<(&'s str, Span)> {
+    let name = attribute.name.source_text(source_text);
+    let name = name.strip_suffix(":list").unwrap_or(name);
+
+    attribute
+        .value
+        .as_ref()
+        .filter(|value| value.kind == MarkupValueKind::Expression && visitor.match_attr(name))
+        .map(|value| (name, value.span))
+}
@@ -0,0 +1,65 @@
+use std::fmt;
+
+use dprint_core::formatting::utils::string_utils;
+use oxc::diagnostics::OxcDiagnostic;
+
+/// A single syntax error, 1-indexed like editors display it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseDiagnostic {
+    pub message: String,
+    pub line: usize,
+    pub column: usize,
+}
+
+/// The source could not be parsed, nothing is formatted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub diagnostics: Vec<ParseDiagnostic>,
+}
+
+impl ParseError {
+    pub fn from_oxc(source_text: &str, errors: &[OxcDiagnostic], panicked: bool) -> Self {
+        let mut diagnostics: Vec<_> = errors
+            .iter()
+            .map(|error| {
+                let offset = error
+                    .labels
+                    .as_ref()
+                    .and_then(|labels| labels.first())
+                    .map_or(0, |label| label.offset());
+
+                ParseDiagnostic {
+                    message: error.message.to_string(),
+                    line: string_utils::get_line_number_of_pos(source_text, offset),
+                    column: string_utils::get_column_number_of_pos(source_text, offset),
+                }
+            })
+            .collect();
+
+        if diagnostics.is_empty() && panicked {
+            diagnostics.push(ParseDiagnostic {
+                message: String::from("Parser stopped unexpectedly"),
+                line: 1,
+                column: 1,
+            });
+        }
+
+        Self { diagnostics }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Failed to parse source")?;
+        for diagnostic in &self.diagnostics {
+            write!(
+                f,
+                "\n  {}:{}: {}",
+                diagnostic.line, diagnostic.column, diagnostic.message
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ParseError {}
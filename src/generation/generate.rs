@@ -1,36 +1,138 @@
-use dprint_core::formatting::PrintItems;
-use oxc::{allocator::Allocator, ast_visit::Visit, parser::Parser, span::SourceType};
-use std::path::Path;
+use dprint_core::formatting::{PrintItems, ir_helpers};
+use oxc::{
+    allocator::Allocator,
+    ast::ast::Program,
+    ast_visit::Visit,
+    parser::Parser,
+    span::{GetSpan, SourceType},
+};
+use std::{ops::Range, path::Path};
 
 use crate::{
     configuration::Configuration,
     generation::{
-        visitor::Visitor,
+        astro,
+        error::ParseError,
+        html, svelte,
+        visitor::{self, Visitor},
+        vue,
         wrapper::{Wrapper, WrapperOption},
     },
 };
 
+/// Error for a file the plugin cannot parse, without reading its text
+pub fn check_file_type(path: &Path) -> anyhow::Result<()> {
+    match file_extension(path).as_deref() {
+        Some("html" | "htm" | "vue" | "svelte" | "astro") => Ok(()),
+        _ => script_source_type(path).map(|_| ()),
+    }
+}
+
 pub fn generate(
     path: &Path,
     source_text: &str,
     config: &Configuration,
+    range: Option<&Range<usize>>,
+) -> anyhow::Result<PrintItems> {
+    match file_extension(path).as_deref() {
+        Some("html" | "htm") => html::generate(source_text, config, range),
+        Some("vue") => vue::generate(source_text, config, range),
+        Some("svelte") => svelte::generate(source_text, config, range),
+        Some("astro") => astro::generate(source_text, config, range),
+        _ => generate_script(path, source_text, config, range),
+    }
+}
+
+fn generate_script(
+    path: &Path,
+    source_text: &str,
+    config: &Configuration,
+    range: Option<&Range<usize>>,
 ) -> anyhow::Result<PrintItems> {
     let allocator = Allocator::default();
-    let source_type = SourceType::from_path(path).unwrap();
+    let source_type = script_source_type(path)?;
     let parsed = Parser::new(&allocator, source_text, source_type).parse();
+    if parsed.panicked || !parsed.errors.is_empty() {
+        return Err(ParseError::from_oxc(source_text, &parsed.errors, parsed.panicked).into());
+    }
+
     let program = parsed.program;
-    let mut visitor = Visitor::new(source_text, config).with_wrapper(if config.enable_wrap {
-        Some(Wrapper::new(WrapperOption {
-            allow_line_overflow: config.allow_line_overflow,
-            indent_to_quote: config.indent_to_quote,
-            indent_width: config.indent_width,
-            line_width_includes_indent: config.line_width_includes_indent,
-            line_width: config.line_width,
-        }))
-    } else {
-        None
-    });
+    if has_ignore_file_comment(source_text, &program) {
+        return Ok(ir_helpers::gen_from_string(source_text));
+    }
+
+    let mut visitor = create_visitor(source_text, config, range).with_comments(&program.comments);
     visitor.visit_program(&program);
 
     Ok(visitor.print_items())
 }
+
+fn file_extension(path: &Path) -> Option<String> {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .map(str::to_ascii_lowercase)
+}
+
+fn script_source_type(path: &Path) -> anyhow::Result<SourceType> {
+    SourceType::from_path(path)
+        .map_err(|err| anyhow::anyhow!("Unsupported file {}: {err}", path.display()))
+}
+
+/// `// classname-wrap-ignore-file` before the first statement
+fn has_ignore_file_comment(source_text: &str, program: &Program<'_>) -> bool {
+    let code_start = program
+        .body
+        .first()
+        .map_or(program.span.end, |statement| statement.span().start);
+
+    program
+        .comments
+        .iter()
+        .take_while(|comment| comment.span.end <= code_start)
+        .any(|comment| {
+            comment.content_span().source_text(source_text).trim() == visitor::IGNORE_FILE_COMMENT
+        })
+}
+
+/// The wrapper is always set, with `enable_wrap` off it collapses every class
+/// list onto a single line, so the output never depends on old line breaks.
+pub fn create_visitor<'a>(
+    source_text: &'a str,
+    config: &'a Configuration,
+    range: Option<&Range<usize>>,
+) -> Visitor<'a> {
+    let option = WrapperOption {
+        use_tabs: config.use_tabs,
+        enable_wrap: config.enable_wrap,
+        sort_classes: config.sort_classes,
+        allow_line_overflow: config.allow_line_overflow,
+        indent_to_quote: config.indent_to_quote,
+        // a `Configuration` built in code skips the checks of `resolve_config`,
+        // columns are divided by the indent width
+        indent_width: config.indent_width.max(1),
+        line_width_includes_indent: config.line_width_includes_indent,
+        line_width: config.line_width,
+    };
+    let attribute_options = config
+        .attribute_overrides
+        .iter()
+        .map(|(name, overrides)| {
+            let attribute_option = WrapperOption {
+                enable_wrap: overrides.enable_wrap.unwrap_or(option.enable_wrap),
+                allow_line_overflow: overrides
+                    .allow_line_overflow
+                    .unwrap_or(option.allow_line_overflow),
+                indent_to_quote: overrides.indent_to_quote.unwrap_or(option.indent_to_quote),
+                line_width: overrides.line_width.unwrap_or(option.line_width),
+                ..option
+            };
+            (name.clone(), attribute_option)
+        })
+        .collect();
+
+    Visitor::new(source_text, config)
+        .with_wrapper(Some(
+            Wrapper::new(option).with_attribute_options(attribute_options),
+        ))
+        .with_range(range.cloned())
+}
@@ -0,0 +1,21 @@
+use dprint_core::formatting::PrintItems;
+use std::ops::Range;
+
+use crate::{
+    configuration::Configuration,
+    generation::{generate::create_visitor, markup::MarkupTokenizer},
+};
+
+pub fn generate(
+    source_text: &str,
+    config: &Configuration,
+    range: Option<&Range<usize>>,
+) -> anyhow::Result<PrintItems> {
+    let mut visitor = create_visitor(source_text, config, range);
+
+    for element in MarkupTokenizer::new(source_text) {
+        visitor.visit_markup_element(&element);
+    }
+
+    Ok(visitor.finish())
+}
@@ -0,0 +1,395 @@
+use oxc::{
+    ast::ast::{Program, Statement},
+    span::{GetSpan, Span},
+};
+
+use crate::generation::types::IntoU32;
+
+/// Elements whose content is raw text and never contains markup
+const RAW_TEXT_ELEMENTS: &[&str] = &["script", "style", "textarea", "title"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarkupValueKind {
+    /// `"..."` or `'...'`, the span includes the quotes
+    Quoted,
+    /// `{...}`, the span includes the braces
+    Expression,
+    Unquoted,
+}
+
+#[derive(Debug, Clone)]
+pub struct MarkupValue {
+    pub span: Span,
+    pub kind: MarkupValueKind,
+    /// `{...}` inside a quoted value (Svelte)
+    pub interpolations: Vec<Span>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MarkupAttribute {
+    pub name: Span,
+    pub value: Option<MarkupValue>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MarkupElement {
+    /// `<` of the opening tag
+    pub start: u32,
+    pub name: Span,
+    pub attributes: Vec<MarkupAttribute>,
+    /// Content of `script`, `style` and other raw text elements
+    pub raw_text: Option<Span>,
+}
+
+/// Expressions in text content which may contain a `<` that is not a tag
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextExpressions {
+    None,
+    /// `{{ ... }}` (Vue)
+    Mustache,
+    /// `{ ... }`, also inside quoted attribute values (Svelte)
+    Braces,
+}
+
+/// A lightweight, forgiving tokenizer for opening tags of HTML-like templates.
+///
+/// Attribute values wrapped in `{...}` (Svelte, Astro) are skipped as a whole,
+/// so a `>` inside an expression does not end the tag.
+pub struct MarkupTokenizer<'a> {
+    source_text: &'a str,
+    pos: usize,
+    text_expressions: TextExpressions,
+}
+
+impl<'a> MarkupTokenizer<'a> {
+    pub fn new(source_text: &'a str) -> Self {
+        Self {
+            source_text,
+            pos: 0,
+            text_expressions: TextExpressions::None,
+        }
+    }
+
+    /// Start tokenizing at `pos` instead of the beginning of the text
+    #[must_use]
+    pub fn with_start(mut self, pos: usize) -> Self {
+        self.pos = pos;
+        self
+    }
+
+    #[must_use]
+    pub fn with_text_expressions(mut self, text_expressions: TextExpressions) -> Self {
+        self.text_expressions = text_expressions;
+        self
+    }
+
+    fn next_tag_offset(&self) -> Option<usize> {
+        let rest = self.rest();
+
+        match self.text_expressions {
+            TextExpressions::None => rest.find('<'),
+            TextExpressions::Mustache | TextExpressions::Braces => rest.find(['<', '{']),
+        }
+    }
+
+    #[inline]
+    fn rest(&self) -> &'a str {
+        self.source_text.get(self.pos..).unwrap_or_default()
+    }
+
+    #[inline]
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn span(&self, start: usize, end: usize) -> Span {
+        Span::new(start.into_u32(), end.into_u32())
+    }
+
+    fn skip_past(&mut self, pattern: &str) {
+        self.pos = match self.rest().find(pattern) {
+            Some(i) => self.pos + i + pattern.len(),
+            None => self.source_text.len(),
+        };
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    /// Skip a `{...}` block starting at the current `{`, respecting nested
+    /// braces and string literals.
+    fn skip_braces(&mut self) {
+        let mut depth = 0usize;
+        let mut quote = None;
+        let mut escaped = false;
+
+        for (i, c) in self.rest().char_indices() {
+            match quote {
+                Some(_) if escaped => escaped = false,
+                Some(_) if c == '\\' => escaped = true,
+                Some(q) if c == q => quote = None,
+                Some(_) => {}
+                None => match c {
+                    '"' | '\'' | '`' => quote = Some(c),
+                    '{' => depth += 1,
+                    '}' => {
+                        depth = depth.saturating_sub(1);
+                        if depth == 0 {
+                            self.pos += i + 1;
+                            return;
+                        }
+                    }
+                    _ => {}
+                },
+            }
+        }
+
+        self.pos = self.source_text.len();
+    }
+
+    /// Read up to and past the closing `quote`, collecting `{...}` on the way
+    fn read_interpolations(&mut self, quote: char) -> Vec<Span> {
+        let mut interpolations = Vec::new();
+
+        while let Some(c) = self.peek() {
+            if c == quote {
+                self.pos += 1;
+                break;
+            } else if c == '{' {
+                let start = self.pos;
+                self.skip_braces();
+                interpolations.push(self.span(start, self.pos));
+            } else {
+                self.pos += c.len_utf8();
+            }
+        }
+
+        interpolations
+    }
+
+    fn read_name(&mut self) -> Span {
+        let start = self.pos;
+        let len = self
+            .rest()
+            .find(|c: char| c.is_whitespace() || matches!(c, '"' | '\'' | '>' | '/' | '='))
+            .unwrap_or(self.rest().len());
+        self.pos += len;
+        self.span(start, self.pos)
+    }
+
+    fn read_value(&mut self) -> Option<MarkupValue> {
+        let start = self.pos;
+
+        match self.peek()? {
+            quote @ ('"' | '\'') => {
+                self.pos += 1;
+                let interpolations = if self.text_expressions == TextExpressions::Braces {
+                    self.read_interpolations(quote)
+                } else {
+                    self.skip_past(&quote.to_string());
+                    Vec::new()
+                };
+                Some(MarkupValue {
+                    span: self.span(start, self.pos),
+                    kind: MarkupValueKind::Quoted,
+                    interpolations,
+                })
+            }
+            '{' => {
+                self.skip_braces();
+                Some(MarkupValue {
+                    span: self.span(start, self.pos),
+                    kind: MarkupValueKind::Expression,
+                    interpolations: Vec::new(),
+                })
+            }
+            _ => {
+                let len = self
+                    .rest()
+                    .find(|c: char| c.is_whitespace() || c == '>')
+                    .unwrap_or(self.rest().len());
+                self.pos += len;
+                Some(MarkupValue {
+                    span: self.span(start, self.pos),
+                    kind: MarkupValueKind::Unquoted,
+                    interpolations: Vec::new(),
+                })
+            }
+        }
+    }
+
+    /// Parse the rest of an opening tag, the current position is after its name
+    fn read_attributes(&mut self) -> Vec<MarkupAttribute> {
+        let mut attributes = Vec::new();
+
+        loop {
+            self.skip_whitespace();
+
+            match self.peek() {
+                None => break,
+                Some('>') => {
+                    self.pos += 1;
+                    break;
+                }
+                Some('/') if self.rest().starts_with("/>") => {
+                    self.pos += 2;
+                    break;
+                }
+                Some('/') => self.pos += 1,
+                // `{spread}` or `{shorthand}` attribute
+                Some('{') => self.skip_braces(),
+                Some(_) => {
+                    let name = self.read_name();
+                    if name.is_empty() {
+                        // stray quote or `=`
+                        self.pos += 1;
+                        continue;
+                    }
+
+                    self.skip_whitespace();
+                    let value = if self.peek() == Some('=') {
+                        self.pos += 1;
+                        self.skip_whitespace();
+                        self.read_value()
+                    } else {
+                        None
+                    };
+
+                    attributes.push(MarkupAttribute { name, value });
+                }
+            }
+        }
+
+        attributes
+    }
+
+    fn read_raw_text(&mut self, name: &str) -> Span {
+        let start = self.pos;
+        let closing = format!("</{name}");
+
+        self.pos = self
+            .rest()
+            .to_ascii_lowercase()
+            .find(&closing)
+            .map_or(self.source_text.len(), |i| self.pos + i);
+        self.span(start, self.pos)
+    }
+}
+
+impl Iterator for MarkupTokenizer<'_> {
+    type Item = MarkupElement;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let offset = self.next_tag_offset()?;
+            self.pos += offset;
+            let rest = self.rest();
+
+            if rest.starts_with("{{") && self.text_expressions == TextExpressions::Mustache {
+                self.skip_past("}}");
+            } else if rest.starts_with('{') && self.text_expressions == TextExpressions::Braces {
+                self.skip_braces();
+            } else if rest.starts_with("<!--") {
+                self.skip_past("-->");
+            } else if rest.starts_with("<!") || rest.starts_with("<?") || rest.starts_with("</") {
+                self.skip_past(">");
+            } else if rest.starts_with('<')
+                && rest[1..].starts_with(|c: char| c.is_ascii_alphabetic())
+            {
+                let start = self.pos.into_u32();
+                self.pos += 1;
+                let name = self.read_name();
+                let attributes = self.read_attributes();
+
+                let tag_name = name.source_text(self.source_text).to_ascii_lowercase();
+                let raw_text = RAW_TEXT_ELEMENTS
+                    .contains(&tag_name.as_str())
+                    .then(|| self.read_raw_text(&tag_name));
+
+                return Some(MarkupElement {
+                    start,
+                    name,
+                    attributes,
+                    raw_text,
+                });
+            } else {
+                self.pos += 1;
+            }
+        }
+    }
+}
+
+/// A copy of the source where only the kept regions survive. Everything else
+/// is blanked with spaces, so positions, lines and columns of the kept code
+/// still match the original source when it is parsed on its own.
+pub struct MaskedSource<'a> {
+    source_text: &'a str,
+    text: Vec<u8>,
+}
+
+impl<'a> MaskedSource<'a> {
+    pub fn new(source_text: &'a str) -> Self {
+        let text = source_text
+            .bytes()
+            .map(|b| if matches!(b, b'\n' | b'\r') { b } else { b' ' })
+            .collect();
+
+        Self { source_text, text }
+    }
+
+    pub fn keep(&mut self, span: Span) {
+        let range = span.start as usize..span.end as usize;
+        self.text[range.clone()].copy_from_slice(&self.source_text.as_bytes()[range]);
+    }
+
+    /// Keep a delimited attribute value (`"..."` or `{...}`) as a standalone
+    /// parenthesized expression statement.
+    pub fn keep_expression(&mut self, value: Span) {
+        let (start, end) = (value.start as usize, value.end as usize);
+        if end < start + 2 {
+            return;
+        }
+
+        self.keep(value);
+        self.text[start] = b'(';
+        self.text[end - 1] = b')';
+        if start > 0 {
+            self.text[start - 1] = b';';
+        }
+        if end < self.text.len() {
+            self.text[end] = b';';
+        }
+    }
+
+    pub fn into_string(self) -> String {
+        // only whole ASCII delimiters are replaced, the rest is valid UTF-8
+        String::from_utf8(self.text).unwrap_or_default()
+    }
+}
+
+/// Top-level statements of the masked program, consumed in source order
+pub struct StatementCursor<'p, 'a> {
+    statements: &'p [Statement<'a>],
+}
+
+impl<'p, 'a> StatementCursor<'p, 'a> {
+    pub fn new(program: &'p Program<'a>) -> Self {
+        Self {
+            statements: &program.body,
+        }
+    }
+
+    /// Statements starting before the end of `span`
+    pub fn take_within(&mut self, span: Span) -> &'p [Statement<'a>] {
+        let count = self
+            .statements
+            .iter()
+            .take_while(|statement| statement.span().start < span.end)
+            .count();
+        let (taken, rest) = self.statements.split_at(count);
+        self.statements = rest;
+        taken
+    }
+}
@@ -0,0 +1,779 @@
+//! Tailwind CSS canonical class order.
+//!
+//! Mirrors the order Tailwind emits its CSS in: classes it does not know
+//! come first, then known classes by layer, variants and property order.
+//! Classes of one property are ordered by root, then keywords by their
+//! position in [`EXACT`]. The sort is stable, so classes that still compare
+//! equal, like `p-2` and `p-4`, keep their order.
+
+/// Core plugins in the order Tailwind generates them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Property {
+    Container,
+    Accessibility,
+    PointerEvents,
+    Visibility,
+    Position,
+    Inset,
+    Isolation,
+    ZIndex,
+    Order,
+    GridColumn,
+    GridColumnStart,
+    GridColumnEnd,
+    GridRow,
+    GridRowStart,
+    GridRowEnd,
+    Float,
+    Clear,
+    Margin,
+    BoxSizing,
+    LineClamp,
+    Display,
+    AspectRatio,
+    Size,
+    Height,
+    MaxHeight,
+    MinHeight,
+    Width,
+    MinWidth,
+    MaxWidth,
+    Flex,
+    FlexShrink,
+    FlexGrow,
+    FlexBasis,
+    TableLayout,
+    CaptionSide,
+    BorderCollapse,
+    BorderSpacing,
+    TransformOrigin,
+    Translate,
+    Rotate,
+    Skew,
+    Scale,
+    Transform,
+    Animation,
+    Cursor,
+    TouchAction,
+    UserSelect,
+    Resize,
+    ScrollSnapType,
+    ScrollSnapAlign,
+    ScrollSnapStop,
+    ScrollMargin,
+    ScrollPadding,
+    ListStylePosition,
+    ListStyleType,
+    ListStyleImage,
+    Appearance,
+    Columns,
+    BreakBefore,
+    BreakInside,
+    BreakAfter,
+    GridAutoColumns,
+    GridAutoFlow,
+    GridAutoRows,
+    GridTemplateColumns,
+    GridTemplateRows,
+    FlexDirection,
+    FlexWrap,
+    PlaceContent,
+    PlaceItems,
+    AlignContent,
+    AlignItems,
+    JustifyContent,
+    JustifyItems,
+    Gap,
+    Space,
+    DivideWidth,
+    DivideStyle,
+    DivideColor,
+    DivideOpacity,
+    PlaceSelf,
+    AlignSelf,
+    JustifySelf,
+    Overflow,
+    Overscroll,
+    ScrollBehavior,
+    TextOverflow,
+    Hyphens,
+    Whitespace,
+    TextWrap,
+    WordBreak,
+    BorderRadius,
+    BorderWidth,
+    BorderStyle,
+    BorderColor,
+    BorderOpacity,
+    BackgroundColor,
+    BackgroundOpacity,
+    BackgroundImage,
+    GradientColorStops,
+    BoxDecorationBreak,
+    BackgroundSize,
+    BackgroundAttachment,
+    BackgroundClip,
+    BackgroundPosition,
+    BackgroundRepeat,
+    BackgroundOrigin,
+    Fill,
+    Stroke,
+    StrokeWidth,
+    ObjectFit,
+    ObjectPosition,
+    Padding,
+    TextAlign,
+    TextIndent,
+    VerticalAlign,
+    FontFamily,
+    FontSize,
+    FontWeight,
+    TextTransform,
+    FontStyle,
+    FontVariantNumeric,
+    LineHeight,
+    LetterSpacing,
+    TextColor,
+    TextOpacity,
+    TextDecoration,
+    TextDecorationColor,
+    TextDecorationStyle,
+    TextDecorationThickness,
+    TextUnderlineOffset,
+    FontSmoothing,
+    PlaceholderColor,
+    PlaceholderOpacity,
+    CaretColor,
+    AccentColor,
+    Opacity,
+    BackgroundBlendMode,
+    MixBlendMode,
+    BoxShadow,
+    BoxShadowColor,
+    OutlineStyle,
+    OutlineWidth,
+    OutlineOffset,
+    OutlineColor,
+    RingWidth,
+    RingColor,
+    RingOpacity,
+    RingOffsetWidth,
+    RingOffsetColor,
+    Blur,
+    Brightness,
+    Contrast,
+    DropShadow,
+    Grayscale,
+    HueRotate,
+    Invert,
+    Saturate,
+    Sepia,
+    Filter,
+    BackdropFilter,
+    Transition,
+    TransitionDelay,
+    TransitionDuration,
+    TransitionTimingFunction,
+    WillChange,
+    Content,
+}
+
+impl Property {
+    /// `components` layer before `utilities` layer
+    #[inline]
+    const fn layer(self) -> u8 {
+        match self {
+            Self::Container => 0,
+            _ => 1,
+        }
+    }
+}
+
+/// Variants in the order Tailwind registers them, `-` suffixed entries match by prefix.
+const VARIANTS: &[&str] = &[
+    "*",
+    "first-letter",
+    "first-line",
+    "marker",
+    "selection",
+    "file",
+    "placeholder",
+    "backdrop",
+    "before",
+    "after",
+    "first",
+    "last",
+    "only",
+    "odd",
+    "even",
+    "first-of-type",
+    "last-of-type",
+    "only-of-type",
+    "visited",
+    "target",
+    "open",
+    "default",
+    "checked",
+    "indeterminate",
+    "placeholder-shown",
+    "autofill",
+    "optional",
+    "required",
+    "valid",
+    "invalid",
+    "in-range",
+    "out-of-range",
+    "read-only",
+    "empty",
+    "focus-within",
+    "hover",
+    "focus",
+    "focus-visible",
+    "active",
+    "enabled",
+    "disabled",
+    "group-",
+    "peer-",
+    "has-",
+    "aria-",
+    "data-",
+    "ltr",
+    "rtl",
+    "motion-safe",
+    "motion-reduce",
+    "dark",
+    "print",
+    "max-",
+    "sm",
+    "md",
+    "lg",
+    "xl",
+    "2xl",
+    "min-",
+    "supports-",
+    "contrast-more",
+    "contrast-less",
+    "portrait",
+    "landscape",
+    "forced-colors",
+];
+
+/// Custom and arbitrary variants sort after every known one
+const UNKNOWN_VARIANT_BIT: u32 = 127;
+
+const EXACT: &[(&str, Property)] = &[
+    ("container", Property::Container),
+    ("sr-only", Property::Accessibility),
+    ("not-sr-only", Property::Accessibility),
+    ("visible", Property::Visibility),
+    ("invisible", Property::Visibility),
+    ("collapse", Property::Visibility),
+    ("static", Property::Position),
+    ("fixed", Property::Position),
+    ("absolute", Property::Position),
+    ("relative", Property::Position),
+    ("sticky", Property::Position),
+    ("isolate", Property::Isolation),
+    ("isolation-auto", Property::Isolation),
+    ("box-border", Property::BoxSizing),
+    ("box-content", Property::BoxSizing),
+    ("block", Property::Display),
+    ("inline-block", Property::Display),
+    ("inline", Property::Display),
+    ("flex", Property::Display),
+    ("inline-flex", Property::Display),
+    ("table", Property::Display),
+    ("inline-table", Property::Display),
+    ("table-caption", Property::Display),
+    ("table-cell", Property::Display),
+    ("table-column", Property::Display),
+    ("table-column-group", Property::Display),
+    ("table-footer-group", Property::Display),
+    ("table-header-group", Property::Display),
+    ("table-row-group", Property::Display),
+    ("table-row", Property::Display),
+    ("flow-root", Property::Display),
+    ("grid", Property::Display),
+    ("inline-grid", Property::Display),
+    ("contents", Property::Display),
+    ("list-item", Property::Display),
+    ("hidden", Property::Display),
+    ("grow", Property::FlexGrow),
+    ("shrink", Property::FlexShrink),
+    ("table-auto", Property::TableLayout),
+    ("table-fixed", Property::TableLayout),
+    ("border-collapse", Property::BorderCollapse),
+    ("border-separate", Property::BorderCollapse),
+    ("transform", Property::Transform),
+    ("transform-cpu", Property::Transform),
+    ("transform-gpu", Property::Transform),
+    ("transform-none", Property::Transform),
+    ("resize", Property::Resize),
+    ("scroll-auto", Property::ScrollBehavior),
+    ("scroll-smooth", Property::ScrollBehavior),
+    ("divide-x", Property::DivideWidth),
+    ("divide-y", Property::DivideWidth),
+    ("truncate", Property::TextOverflow),
+    ("break-normal", Property::WordBreak),
+    ("break-words", Property::WordBreak),
+    ("break-all", Property::WordBreak),
+    ("break-keep", Property::WordBreak),
+    ("rounded", Property::BorderRadius),
+    ("border", Property::BorderWidth),
+    ("box-decoration-slice", Property::BoxDecorationBreak),
+    ("box-decoration-clone", Property::BoxDecorationBreak),
+    ("uppercase", Property::TextTransform),
+    ("lowercase", Property::TextTransform),
+    ("capitalize", Property::TextTransform),
+    ("normal-case", Property::TextTransform),
+    ("italic", Property::FontStyle),
+    ("not-italic", Property::FontStyle),
+    ("normal-nums", Property::FontVariantNumeric),
+    ("ordinal", Property::FontVariantNumeric),
+    ("slashed-zero", Property::FontVariantNumeric),
+    ("lining-nums", Property::FontVariantNumeric),
+    ("oldstyle-nums", Property::FontVariantNumeric),
+    ("proportional-nums", Property::FontVariantNumeric),
+    ("tabular-nums", Property::FontVariantNumeric),
+    ("diagonal-fractions", Property::FontVariantNumeric),
+    ("stacked-fractions", Property::FontVariantNumeric),
+    ("underline", Property::TextDecoration),
+    ("overline", Property::TextDecoration),
+    ("line-through", Property::TextDecoration),
+    ("no-underline", Property::TextDecoration),
+    ("antialiased", Property::FontSmoothing),
+    ("subpixel-antialiased", Property::FontSmoothing),
+    ("shadow", Property::BoxShadow),
+    ("outline", Property::OutlineStyle),
+    ("ring", Property::RingWidth),
+    ("ring-inset", Property::RingWidth),
+    ("blur", Property::Blur),
+    ("drop-shadow", Property::DropShadow),
+    ("grayscale", Property::Grayscale),
+    ("invert", Property::Invert),
+    ("sepia", Property::Sepia),
+    ("filter", Property::Filter),
+    ("filter-none", Property::Filter),
+    ("backdrop-filter", Property::BackdropFilter),
+    ("backdrop-filter-none", Property::BackdropFilter),
+    ("transition", Property::Transition),
+];
+
+// roots sharing a property, in the order Tailwind generates them
+const INSET_ROOTS: &[&str] = &[
+    "inset", "inset-x", "inset-y", "start", "end", "top", "right", "bottom", "left",
+];
+const GRID_COLUMN_ROOTS: &[&str] = &["col", "col-span"];
+const GRID_ROW_ROOTS: &[&str] = &["row", "row-span"];
+const MARGIN_ROOTS: &[&str] = &["m", "mx", "my", "ms", "me", "mt", "mr", "mb", "ml"];
+const FLEX_SHRINK_ROOTS: &[&str] = &["shrink", "flex-shrink"];
+const FLEX_GROW_ROOTS: &[&str] = &["grow", "flex-grow"];
+const BORDER_SPACING_ROOTS: &[&str] = &["border-spacing", "border-spacing-x", "border-spacing-y"];
+const TRANSLATE_ROOTS: &[&str] = &["translate-x", "translate-y"];
+const SKEW_ROOTS: &[&str] = &["skew-x", "skew-y"];
+const SCALE_ROOTS: &[&str] = &["scale", "scale-x", "scale-y"];
+const SCROLL_MARGIN_ROOTS: &[&str] = &[
+    "scroll-m",
+    "scroll-mx",
+    "scroll-my",
+    "scroll-ms",
+    "scroll-me",
+    "scroll-mt",
+    "scroll-mr",
+    "scroll-mb",
+    "scroll-ml",
+];
+const SCROLL_PADDING_ROOTS: &[&str] = &[
+    "scroll-p",
+    "scroll-px",
+    "scroll-py",
+    "scroll-ps",
+    "scroll-pe",
+    "scroll-pt",
+    "scroll-pr",
+    "scroll-pb",
+    "scroll-pl",
+];
+const GAP_ROOTS: &[&str] = &["gap", "gap-x", "gap-y"];
+const SPACE_ROOTS: &[&str] = &["space-x", "space-y"];
+const DIVIDE_WIDTH_ROOTS: &[&str] = &["divide-x", "divide-y"];
+const OVERFLOW_ROOTS: &[&str] = &["overflow", "overflow-x", "overflow-y"];
+const OVERSCROLL_ROOTS: &[&str] = &["overscroll", "overscroll-x", "overscroll-y"];
+const BORDER_RADIUS_ROOTS: &[&str] = &[
+    "rounded",
+    "rounded-s",
+    "rounded-e",
+    "rounded-t",
+    "rounded-r",
+    "rounded-b",
+    "rounded-l",
+    "rounded-ss",
+    "rounded-se",
+    "rounded-ee",
+    "rounded-es",
+    "rounded-tl",
+    "rounded-tr",
+    "rounded-br",
+    "rounded-bl",
+];
+const BORDER_WIDTH_ROOTS: &[&str] = &[
+    "border", "border-x", "border-y", "border-s", "border-e", "border-t", "border-r", "border-b",
+    "border-l",
+];
+const GRADIENT_ROOTS: &[&str] = &["from", "via", "to"];
+const PADDING_ROOTS: &[&str] = &["p", "px", "py", "ps", "pe", "pt", "pr", "pb", "pl"];
+const BACKDROP_FILTER_ROOTS: &[&str] = &[
+    "backdrop-blur",
+    "backdrop-brightness",
+    "backdrop-contrast",
+    "backdrop-grayscale",
+    "backdrop-hue-rotate",
+    "backdrop-invert",
+    "backdrop-opacity",
+    "backdrop-saturate",
+    "backdrop-sepia",
+];
+
+/// Breaks ties between classes of one property, `m-2` before `mx-2` before `mt-2`
+const ROOT_GROUPS: &[&[&str]] = &[
+    INSET_ROOTS,
+    GRID_COLUMN_ROOTS,
+    GRID_ROW_ROOTS,
+    MARGIN_ROOTS,
+    FLEX_SHRINK_ROOTS,
+    FLEX_GROW_ROOTS,
+    BORDER_SPACING_ROOTS,
+    TRANSLATE_ROOTS,
+    SKEW_ROOTS,
+    SCALE_ROOTS,
+    SCROLL_MARGIN_ROOTS,
+    SCROLL_PADDING_ROOTS,
+    GAP_ROOTS,
+    SPACE_ROOTS,
+    DIVIDE_WIDTH_ROOTS,
+    OVERFLOW_ROOTS,
+    OVERSCROLL_ROOTS,
+    BORDER_RADIUS_ROOTS,
+    BORDER_WIDTH_ROOTS,
+    GRADIENT_ROOTS,
+    PADDING_ROOTS,
+    BACKDROP_FILTER_ROOTS,
+];
+
+/// Sort each run of classes between interpolations (`${...}` or `{...}`)
+/// into Tailwind order.
+pub fn sort_classes(classes: &mut [&str]) {
+    for run in classes.split_mut(|class| class.contains('{')) {
+        run.sort_by_cached_key(|class| class_order(class));
+    }
+}
+
+/// `None` for classes Tailwind does not know, which keeps them first
+fn class_order(class: &str) -> Option<(u8, u128, Property, usize, usize)> {
+    let (variants, utility) = split_variants(class);
+    let utility = utility.trim_start_matches('!').trim_end_matches('!');
+    let utility = utility.strip_prefix('-').unwrap_or(utility);
+    let (property, root_rank, keyword_rank) = utility_order(utility)?;
+
+    let variants = variants
+        .iter()
+        .fold(0u128, |mask, variant| mask | (1 << variant_bit(variant)));
+
+    Some((
+        property.layer(),
+        variants,
+        property,
+        root_rank,
+        keyword_rank,
+    ))
+}
+
+/// Split `md:hover:p-4` into `[md, hover]` and `p-4`, ignoring `:` inside brackets.
+fn split_variants(class: &str) -> (Vec<&str>, &str) {
+    let mut variants = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+
+    for (i, c) in class.char_indices() {
+        match c {
+            '[' | '(' => depth += 1,
+            ']' | ')' => depth = depth.saturating_sub(1),
+            ':' if depth == 0 => {
+                variants.push(&class[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+
+    (variants, &class[start..])
+}
+
+fn variant_bit(variant: &str) -> u32 {
+    VARIANTS
+        .iter()
+        .position(|known| match known.strip_suffix('-') {
+            Some(prefix) if !prefix.is_empty() => variant.starts_with(known),
+            _ => variant == *known,
+        })
+        .map_or(UNKNOWN_VARIANT_BIT, |i| i as u32)
+}
+
+/// The property, the position of the root in its [`ROOT_GROUPS`] entry and the
+/// position in [`EXACT`], keywords sort before values of the same root
+fn utility_order(utility: &str) -> Option<(Property, usize, usize)> {
+    if let Some(i) = EXACT.iter().position(|(name, _)| *name == utility) {
+        return Some((EXACT[i].1, root_rank(utility), i));
+    }
+
+    // try the longest root first, `inset-x-0` is `inset-x` before `inset`
+    utility
+        .char_indices()
+        .rev()
+        .filter(|(_, c)| *c == '-')
+        .find_map(|(i, _)| {
+            let root = &utility[..i];
+            functional_property(root, &utility[i + 1..])
+                .map(|property| (property, root_rank(root), EXACT.len()))
+        })
+}
+
+fn root_rank(root: &str) -> usize {
+    ROOT_GROUPS
+        .iter()
+        .find_map(|group| group.iter().position(|known| *known == root))
+        .unwrap_or(0)
+}
+
+fn functional_property(root: &str, value: &str) -> Option<Property> {
+    if value.is_empty() {
+        return None;
+    }
+
+    let property = match root {
+        "pointer-events" => Property::PointerEvents,
+        root if INSET_ROOTS.contains(&root) => Property::Inset,
+        "z" => Property::ZIndex,
+        "order" => Property::Order,
+        root if GRID_COLUMN_ROOTS.contains(&root) => Property::GridColumn,
+        "col-start" => Property::GridColumnStart,
+        "col-end" => Property::GridColumnEnd,
+        root if GRID_ROW_ROOTS.contains(&root) => Property::GridRow,
+        "row-start" => Property::GridRowStart,
+        "row-end" => Property::GridRowEnd,
+        "float" => Property::Float,
+        "clear" => Property::Clear,
+        root if MARGIN_ROOTS.contains(&root) => Property::Margin,
+        "line-clamp" => Property::LineClamp,
+        "aspect" => Property::AspectRatio,
+        "size" => Property::Size,
+        "h" => Property::Height,
+        "max-h" => Property::MaxHeight,
+        "min-h" => Property::MinHeight,
+        "w" => Property::Width,
+        "min-w" => Property::MinWidth,
+        "max-w" => Property::MaxWidth,
+        "flex" => match value {
+            "row" | "row-reverse" | "col" | "col-reverse" => Property::FlexDirection,
+            "wrap" | "wrap-reverse" | "nowrap" => Property::FlexWrap,
+            _ => Property::Flex,
+        },
+        root if FLEX_SHRINK_ROOTS.contains(&root) => Property::FlexShrink,
+        root if FLEX_GROW_ROOTS.contains(&root) => Property::FlexGrow,
+        "basis" => Property::FlexBasis,
+        "caption" => Property::CaptionSide,
+        root if BORDER_SPACING_ROOTS.contains(&root) => Property::BorderSpacing,
+        "origin" => Property::TransformOrigin,
+        root if TRANSLATE_ROOTS.contains(&root) => Property::Translate,
+        "rotate" => Property::Rotate,
+        root if SKEW_ROOTS.contains(&root) => Property::Skew,
+        root if SCALE_ROOTS.contains(&root) => Property::Scale,
+        "animate" => Property::Animation,
+        "cursor" => Property::Cursor,
+        "touch" => Property::TouchAction,
+        "select" => Property::UserSelect,
+        "resize" => Property::Resize,
+        "snap" => match value {
+            "start" | "end" | "center" | "align-none" => Property::ScrollSnapAlign,
+            "normal" | "always" => Property::ScrollSnapStop,
+            _ => Property::ScrollSnapType,
+        },
+        root if SCROLL_MARGIN_ROOTS.contains(&root) => Property::ScrollMargin,
+        root if SCROLL_PADDING_ROOTS.contains(&root) => Property::ScrollPadding,
+        "list" => match value {
+            "inside" | "outside" => Property::ListStylePosition,
+            _ => Property::ListStyleType,
+        },
+        "list-image" => Property::ListStyleImage,
+        "appearance" => Property::Appearance,
+        "columns" => Property::Columns,
+        "break-before" => Property::BreakBefore,
+        "break-inside" => Property::BreakInside,
+        "break-after" => Property::BreakAfter,
+        "auto-cols" => Property::GridAutoColumns,
+        "grid-flow" => Property::GridAutoFlow,
+        "auto-rows" => Property::GridAutoRows,
+        "grid-cols" => Property::GridTemplateColumns,
+        "grid-rows" => Property::GridTemplateRows,
+        "place-content" => Property::PlaceContent,
+        "place-items" => Property::PlaceItems,
+        "content" => match value {
+            "center" | "start" | "end" | "between" | "around" | "evenly" | "baseline"
+            | "stretch" | "normal" => Property::AlignContent,
+            _ => Property::Content,
+        },
+        "items" => Property::AlignItems,
+        "justify" => Property::JustifyContent,
+        "justify-items" => Property::JustifyItems,
+        root if GAP_ROOTS.contains(&root) => Property::Gap,
+        root if SPACE_ROOTS.contains(&root) => Property::Space,
+        root if DIVIDE_WIDTH_ROOTS.contains(&root) => Property::DivideWidth,
+        "divide" => match value {
+            "solid" | "dashed" | "dotted" | "double" | "none" => Property::DivideStyle,
+            _ => Property::DivideColor,
+        },
+        "divide-opacity" => Property::DivideOpacity,
+        "place-self" => Property::PlaceSelf,
+        "self" => Property::AlignSelf,
+        "justify-self" => Property::JustifySelf,
+        root if OVERFLOW_ROOTS.contains(&root) => Property::Overflow,
+        root if OVERSCROLL_ROOTS.contains(&root) => Property::Overscroll,
+        "hyphens" => Property::Hyphens,
+        "whitespace" => Property::Whitespace,
+        root if BORDER_RADIUS_ROOTS.contains(&root) => Property::BorderRadius,
+        "border" => match value {
+            "solid" | "dashed" | "dotted" | "double" | "hidden" | "none" => Property::BorderStyle,
+            _ if is_length(value) => Property::BorderWidth,
+            _ => Property::BorderColor,
+        },
+        root if BORDER_WIDTH_ROOTS.contains(&root) => {
+            if is_length(value) {
+                Property::BorderWidth
+            } else {
+                Property::BorderColor
+            }
+        }
+        "border-opacity" => Property::BorderOpacity,
+        "bg" => match value {
+            "fixed" | "local" | "scroll" => Property::BackgroundAttachment,
+            "bottom" | "center" | "left" | "left-bottom" | "left-top" | "right"
+            | "right-bottom" | "right-top" | "top" => Property::BackgroundPosition,
+            "repeat" | "no-repeat" | "repeat-x" | "repeat-y" | "repeat-round" | "repeat-space" => {
+                Property::BackgroundRepeat
+            }
+            "auto" | "cover" | "contain" => Property::BackgroundSize,
+            "none" => Property::BackgroundImage,
+            _ => Property::BackgroundColor,
+        },
+        "bg-opacity" => Property::BackgroundOpacity,
+        "bg-gradient-to" => Property::BackgroundImage,
+        "bg-clip" => Property::BackgroundClip,
+        "bg-origin" => Property::BackgroundOrigin,
+        "bg-blend" => Property::BackgroundBlendMode,
+        root if GRADIENT_ROOTS.contains(&root) => Property::GradientColorStops,
+        "fill" => Property::Fill,
+        "stroke" => {
+            if is_length(value) {
+                Property::StrokeWidth
+            } else {
+                Property::Stroke
+            }
+        }
+        "object" => match value {
+            "contain" | "cover" | "fill" | "none" | "scale-down" => Property::ObjectFit,
+            _ => Property::ObjectPosition,
+        },
+        root if PADDING_ROOTS.contains(&root) => Property::Padding,
+        "text" => match value {
+            "left" | "center" | "right" | "justify" | "start" | "end" => Property::TextAlign,
+            "ellipsis" | "clip" => Property::TextOverflow,
+            "wrap" | "nowrap" | "balance" | "pretty" => Property::TextWrap,
+            "xs" | "sm" | "base" | "lg" | "xl" => Property::FontSize,
+            _ if value.ends_with("xl") && value[..value.len() - 2].parse::<u8>().is_ok() => {
+                Property::FontSize
+            }
+            _ if is_length(value) => Property::FontSize,
+            _ => Property::TextColor,
+        },
+        "text-opacity" => Property::TextOpacity,
+        "indent" => Property::TextIndent,
+        "align" => Property::VerticalAlign,
+        "font" => match value {
+            "thin" | "extralight" | "light" | "normal" | "medium" | "semibold" | "bold"
+            | "extrabold" | "black" => Property::FontWeight,
+            _ if is_length(value) => Property::FontWeight,
+            _ => Property::FontFamily,
+        },
+        "leading" => Property::LineHeight,
+        "tracking" => Property::LetterSpacing,
+        "decoration" => match value {
+            "solid" | "double" | "dotted" | "dashed" | "wavy" => Property::TextDecorationStyle,
+            "auto" | "from-font" => Property::TextDecorationThickness,
+            _ if is_length(value) => Property::TextDecorationThickness,
+            _ => Property::TextDecorationColor,
+        },
+        "underline-offset" => Property::TextUnderlineOffset,
+        "placeholder" => Property::PlaceholderColor,
+        "placeholder-opacity" => Property::PlaceholderOpacity,
+        "caret" => Property::CaretColor,
+        "accent" => Property::AccentColor,
+        "opacity" => Property::Opacity,
+        "mix-blend" => Property::MixBlendMode,
+        "shadow" => match value {
+            "sm" | "md" | "lg" | "xl" | "2xl" | "inner" | "none" => Property::BoxShadow,
+            _ => Property::BoxShadowColor,
+        },
+        "outline" => match value {
+            "none" | "dashed" | "dotted" | "double" => Property::OutlineStyle,
+            _ if is_length(value) => Property::OutlineWidth,
+            _ => Property::OutlineColor,
+        },
+        "outline-offset" => Property::OutlineOffset,
+        "ring" => {
+            if is_length(value) {
+                Property::RingWidth
+            } else {
+                Property::RingColor
+            }
+        }
+        "ring-opacity" => Property::RingOpacity,
+        "ring-offset" => {
+            if is_length(value) {
+                Property::RingOffsetWidth
+            } else {
+                Property::RingOffsetColor
+            }
+        }
+        "blur" => Property::Blur,
+        "brightness" => Property::Brightness,
+        "contrast" => Property::Contrast,
+        "drop-shadow" => Property::DropShadow,
+        "grayscale" => Property::Grayscale,
+        "hue-rotate" => Property::HueRotate,
+        "invert" => Property::Invert,
+        "saturate" => Property::Saturate,
+        "sepia" => Property::Sepia,
+        root if BACKDROP_FILTER_ROOTS.contains(&root) => Property::BackdropFilter,
+        "transition" => Property::Transition,
+        "delay" => Property::TransitionDelay,
+        "duration" => Property::TransitionDuration,
+        "ease" => Property::TransitionTimingFunction,
+        "will-change" => Property::WillChange,
+        _ => return None,
+    };
+
+    Some(property)
+}
+
+/// `2`, `px`, `0.5` or an arbitrary length such as `[3px]`
+fn is_length(value: &str) -> bool {
+    let value = value
+        .strip_prefix('[')
+        .and_then(|value| value.strip_suffix(']'))
+        .map(|value| value.strip_prefix("length:").unwrap_or(value))
+        .unwrap_or(value);
+
+    value == "px"
+        || value.starts_with("calc(")
+        || value.starts_with(|c: char| c.is_ascii_digit() || c == '.')
+}
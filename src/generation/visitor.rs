@@ -1,22 +1,34 @@
 use dprint_core::formatting::{PrintItems, ir_helpers};
 use oxc::{
     ast::{
-        AstKind,
-        ast::{JSXAttribute, JSXAttributeValue, JSXElement, JSXExpression},
+        AstKind, Comment,
+        ast::{
+            ArrayExpressionElement, CallExpression, Expression, JSXAttribute, JSXAttributeValue,
+            JSXElement, JSXExpression, ObjectPropertyKind, PropertyKey, StringLiteral,
+            TemplateLiteral,
+        },
     },
     ast_visit::{
         Visit,
-        walk::{walk_jsx_attribute, walk_jsx_element},
+        walk::{walk_call_expression, walk_jsx_attribute, walk_jsx_element},
     },
-    span::{Atom, Span},
+    span::{GetSpan, Span},
 };
+use std::ops::Range;
 
-use crate::{configuration::Configuration, generation::wrapper::Wrapper};
+use crate::{
+    configuration::Configuration,
+    generation::{
+        markup::{MarkupAttribute, MarkupElement, MarkupValueKind},
+        wrapper::Wrapper,
+    },
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AttributeContext {
     StringLiteral,
     JSXExpression,
+    TemplateLiteral,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -24,6 +36,7 @@ pub struct AttributePos<'a> {
     source_text: &'a str,
     attr_name_span: &'a Span,
     attr_value_span: &'a Span,
+    interpolation_spans: &'a [Span],
 }
 
 impl<'a> AttributePos<'a> {
@@ -32,9 +45,17 @@ impl<'a> AttributePos<'a> {
             source_text,
             attr_name_span,
             attr_value_span,
+            interpolation_spans: &[],
         }
     }
 
+    /// `${...}` spans of a template literal value, kept as atomic tokens
+    #[must_use]
+    pub fn with_interpolation_spans(mut self, spans: &'a [Span]) -> Self {
+        self.interpolation_spans = spans;
+        self
+    }
+
     pub fn source_text(&self) -> &str {
         self.source_text
     }
@@ -46,14 +67,44 @@ impl<'a> AttributePos<'a> {
     pub fn attr_value_span_start(&self) -> usize {
         self.attr_value_span.start as usize
     }
+
+    pub fn interpolation_spans(&self) -> &[Span] {
+        self.interpolation_spans
+    }
+}
+
+/// A class string found in the arguments of a class function call
+#[derive(Clone, Copy)]
+enum ClassLiteral<'b, 'a> {
+    String(&'b StringLiteral<'a>),
+    Template(&'b TemplateLiteral<'a>),
+}
+
+impl ClassLiteral<'_, '_> {
+    fn span_start(&self) -> u32 {
+        match self {
+            Self::String(literal) => literal.span.start,
+            Self::Template(template) => template.span.start,
+        }
+    }
 }
 
+/// Comments skipping the element or attribute right after them
+const IGNORE_COMMENTS: &[&str] = &["dprint-ignore", "classname-wrap-ignore"];
+/// Comment skipping the whole file, before any code
+pub const IGNORE_FILE_COMMENT: &str = "classname-wrap-ignore-file";
+
 pub struct Visitor<'a> {
     source_text: &'a str,
     print_items: PrintItems,
     wrapper: Option<Wrapper>,
     last_offset: usize,
     config: &'a Configuration,
+    /// `classnameAttributes` entry of the attribute being visited, selects its wrapping options
+    attribute_name: Option<&'a str>,
+    ignore_comments: Vec<Span>,
+    /// Only values intersecting this byte range are formatted
+    range: Option<Range<usize>>,
 }
 
 impl<'a> Visitor<'a> {
@@ -64,6 +115,9 @@ impl<'a> Visitor<'a> {
             wrapper: None,
             last_offset: 0,
             config,
+            attribute_name: None,
+            ignore_comments: Vec::new(),
+            range: None,
         }
     }
 
@@ -73,6 +127,27 @@ impl<'a> Visitor<'a> {
         self
     }
 
+    #[must_use]
+    pub fn with_range(mut self, range: Option<Range<usize>>) -> Self {
+        self.range = range;
+        self
+    }
+
+    /// Keep the ignore comments among `comments`
+    #[must_use]
+    pub fn with_comments(mut self, comments: &[Comment]) -> Self {
+        let source_text = self.source_text;
+        self.ignore_comments = comments
+            .iter()
+            .filter(|comment| {
+                let content = comment.content_span().source_text(source_text).trim();
+                IGNORE_COMMENTS.contains(&content)
+            })
+            .map(|comment| comment.span)
+            .collect();
+        self
+    }
+
     #[must_use]
     pub fn print_items(self) -> PrintItems {
         self.print_items
@@ -81,8 +156,88 @@ impl<'a> Visitor<'a> {
 
 impl<'a> Visitor<'a> {
     #[inline]
-    fn match_attr(&self, target: &str) -> bool {
-        self.config.classname_attributes.contains(target)
+    pub fn match_attr(&self, target: &str) -> bool {
+        self.config.find_classname_attribute(target).is_some()
+    }
+
+    fn match_function(&self, callee: &Expression<'_>) -> bool {
+        match callee {
+            Expression::Identifier(ident) => self
+                .config
+                .classname_functions
+                .contains(ident.name.as_str()),
+            _ => false,
+        }
+    }
+
+    /// Collect class strings from a class function argument, following arrays,
+    /// object keys and values, conditional/logical branches and nested class function calls.
+    fn collect_class_literals<'b>(
+        &self,
+        expression: &'b Expression<'a>,
+        literals: &mut Vec<ClassLiteral<'b, 'a>>,
+    ) {
+        match expression {
+            Expression::StringLiteral(literal) => literals.push(ClassLiteral::String(literal)),
+            Expression::TemplateLiteral(template) => {
+                literals.push(ClassLiteral::Template(template))
+            }
+            Expression::ArrayExpression(array) => {
+                for element in &array.elements {
+                    if let ArrayExpressionElement::SpreadElement(spread) = element {
+                        self.collect_class_literals(&spread.argument, literals);
+                    } else if let Some(element) = element.as_expression() {
+                        self.collect_class_literals(element, literals);
+                    }
+                }
+            }
+            Expression::ObjectExpression(object) => {
+                for property in &object.properties {
+                    if let ObjectPropertyKind::ObjectProperty(property) = property {
+                        if let PropertyKey::StringLiteral(literal) = &property.key {
+                            literals.push(ClassLiteral::String(literal));
+                        }
+                        self.collect_class_literals(&property.value, literals);
+                    }
+                }
+            }
+            Expression::ConditionalExpression(conditional) => {
+                self.collect_class_literals(&conditional.consequent, literals);
+                self.collect_class_literals(&conditional.alternate, literals);
+            }
+            Expression::LogicalExpression(logical) => {
+                self.collect_class_literals(&logical.right, literals);
+            }
+            Expression::ParenthesizedExpression(parenthesized) => {
+                self.collect_class_literals(&parenthesized.expression, literals);
+            }
+            Expression::CallExpression(call) if self.match_function(&call.callee) => {
+                for argument in &call.arguments {
+                    if let Some(argument) = argument.as_expression() {
+                        self.collect_class_literals(argument, literals);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Whether an ignore comment directly precedes `start`, only whitespace or
+    /// the `}` of `{/* dprint-ignore */}` may be in between
+    fn is_ignored(&self, start: u32) -> bool {
+        self.ignore_comments
+            .iter()
+            .take_while(|comment| comment.end <= start)
+            .last()
+            .and_then(|comment| self.source_text.get(comment.end as usize..start as usize))
+            .is_some_and(|between| {
+                let between = between.trim_start();
+                between
+                    .strip_prefix('}')
+                    .unwrap_or(between)
+                    .trim()
+                    .is_empty()
+            })
     }
 
     fn print_pre_text(&mut self, current_span: &Span) {
@@ -98,19 +253,32 @@ impl<'a> Visitor<'a> {
 
     fn print_current_text(
         &mut self,
-        text: &Atom<'_>,
+        text: &str,
         attr_name_span: &Span,
         attr_value_span: &Span,
+        interpolation_spans: &[Span],
         context: AttributeContext,
     ) {
+        let in_range = self.range.as_ref().is_none_or(|range| {
+            let (start, end) = (attr_value_span.start as usize, attr_value_span.end as usize);
+            if range.is_empty() {
+                // a cursor formats the value it touches
+                start <= range.start && range.start <= end
+            } else {
+                start < range.end && range.start < end
+            }
+        });
+
         match &mut self.wrapper {
-            Some(wrapper) => {
-                let attr_pos = AttributePos::new(self.source_text, attr_name_span, attr_value_span);
+            Some(wrapper) if in_range => {
+                let option = wrapper.option_for(self.attribute_name);
+                let attr_pos = AttributePos::new(self.source_text, attr_name_span, attr_value_span)
+                    .with_interpolation_spans(interpolation_spans);
 
                 self.print_items
-                    .extend(wrapper.format(text, attr_pos, context));
+                    .extend(wrapper.format(&option, text, attr_pos, context));
             }
-            None => self.print_items.extend(ir_helpers::gen_from_string(text)),
+            _ => self.print_items.extend(ir_helpers::gen_from_string(text)),
         }
     }
 
@@ -126,12 +294,137 @@ impl<'a> Visitor<'a> {
     fn handle_string_literal(
         &mut self,
         string_literal_span: &Span,
-        raw_text: &Atom<'_>,
+        raw_text: &str,
         attr_name_span: &Span,
         context: AttributeContext,
     ) {
         self.print_pre_text(string_literal_span);
-        self.print_current_text(raw_text, attr_name_span, string_literal_span, context);
+        self.print_current_text(raw_text, attr_name_span, string_literal_span, &[], context);
+    }
+
+    fn handle_class_function_call(&mut self, call: &CallExpression<'a>) {
+        let mut literals = Vec::new();
+        for argument in &call.arguments {
+            if let Some(argument) = argument.as_expression() {
+                self.collect_class_literals(argument, &mut literals);
+            }
+        }
+        self.handle_class_literals(literals, &call.callee.span());
+    }
+
+    fn handle_class_literals(
+        &mut self,
+        mut literals: Vec<ClassLiteral<'_, 'a>>,
+        attr_name_span: &Span,
+    ) {
+        literals.sort_by_key(ClassLiteral::span_start);
+
+        for literal in literals {
+            match literal {
+                ClassLiteral::String(literal) => {
+                    if let Some(raw) = &literal.raw {
+                        self.handle_string_literal(
+                            &literal.span,
+                            raw,
+                            attr_name_span,
+                            AttributeContext::JSXExpression,
+                        );
+                    }
+                }
+                ClassLiteral::Template(template) => {
+                    self.handle_template_literal(template, attr_name_span)
+                }
+            }
+        }
+    }
+
+    fn handle_template_literal(&mut self, template: &TemplateLiteral<'_>, attr_name_span: &Span) {
+        let source_text = self.source_text;
+        let Some(raw_text) =
+            source_text.get(template.span.start as usize..template.span.end as usize)
+        else {
+            return;
+        };
+
+        // the gap between two quasis is exactly the `${...}` of an expression
+        let interpolation_spans: Vec<_> = template
+            .quasis
+            .windows(2)
+            .map(|quasis| Span::new(quasis[0].span.end, quasis[1].span.start))
+            .collect();
+
+        self.print_pre_text(&template.span);
+        self.print_current_text(
+            raw_text,
+            attr_name_span,
+            &template.span,
+            &interpolation_spans,
+            AttributeContext::TemplateLiteral,
+        );
+    }
+}
+
+/// Entry points for markup templates, which are not visited as an oxc AST
+impl<'a> Visitor<'a> {
+    pub fn visit_markup_element(&mut self, element: &MarkupElement) {
+        self.enter_markup_element(element);
+
+        for attribute in &element.attributes {
+            self.visit_markup_attribute(attribute);
+        }
+    }
+
+    pub fn enter_markup_element(&mut self, element: &MarkupElement) {
+        if let Some(wrapper) = &mut self.wrapper {
+            let source_text = self.source_text;
+            let node_span_start = element.start as usize;
+            wrapper.set_pre_jsx_element_line(source_text, node_span_start);
+            wrapper.set_pre_indent_count(source_text, node_span_start);
+        }
+    }
+
+    pub fn visit_markup_attribute(&mut self, attribute: &MarkupAttribute) {
+        let source_text = self.source_text;
+        let attr_name = attribute.name.source_text(source_text);
+
+        if let Some(entry) = self.config.find_classname_attribute(attr_name)
+            && let Some(value) = &attribute.value
+            && value.kind == MarkupValueKind::Quoted
+        {
+            let raw = value.span.source_text(source_text);
+            self.attribute_name = Some(entry);
+            self.print_pre_text(&value.span);
+            self.print_current_text(
+                raw,
+                &attribute.name,
+                &value.span,
+                &value.interpolations,
+                AttributeContext::StringLiteral,
+            );
+            self.attribute_name = None;
+        }
+    }
+
+    /// Format the class strings of a value bound to the class attribute
+    /// `attr_name`, like a class function argument
+    pub fn visit_class_expression(
+        &mut self,
+        expression: &Expression<'a>,
+        attr_name: &str,
+        attr_name_span: &Span,
+    ) {
+        let mut literals = Vec::new();
+        self.collect_class_literals(expression, &mut literals);
+
+        self.attribute_name = self.config.find_classname_attribute(attr_name);
+        self.handle_class_literals(literals, attr_name_span);
+        self.attribute_name = None;
+    }
+
+    #[must_use]
+    pub fn finish(mut self) -> PrintItems {
+        self.print_post_text();
+        self.print_items
     }
 }
 
@@ -143,6 +436,11 @@ impl<'a> Visit<'a> for Visitor<'a> {
     }
 
     fn visit_jsx_element(&mut self, it: &JSXElement<'a>) {
+        // the element is printed verbatim with the rest of the source
+        if self.is_ignored(it.span.start) {
+            return;
+        }
+
         if let Some(wrapper) = &mut self.wrapper {
             let source_text = self.source_text;
             let node_span_start = it.opening_element.span.start as usize;
@@ -152,38 +450,66 @@ impl<'a> Visit<'a> for Visitor<'a> {
         walk_jsx_element(self, it);
     }
 
+    fn visit_call_expression(&mut self, it: &CallExpression<'a>) {
+        if self.match_function(&it.callee) {
+            self.handle_class_function_call(it);
+            // nested class function calls are collected as well
+            return;
+        }
+        walk_call_expression(self, it);
+    }
+
     fn visit_jsx_attribute(&mut self, it: &JSXAttribute<'a>) {
-        let attr_name = it.name.get_identifier();
+        if self.is_ignored(it.span.start) {
+            return;
+        }
+
+        // namespaced names are matched as a whole, `svg:class` is not `class`
+        let attr_name_span = it.name.span();
+        let Some(entry) = self
+            .config
+            .find_classname_attribute(attr_name_span.source_text(self.source_text))
+        else {
+            walk_jsx_attribute(self, it);
+            return;
+        };
 
-        if self.match_attr(attr_name.name.as_str()) {
-            if let Some(value) = it.value.as_ref() {
-                match value {
-                    JSXAttributeValue::StringLiteral(literal) => {
+        let outer_attribute_name = self.attribute_name.replace(entry);
+        if let Some(value) = it.value.as_ref() {
+            match value {
+                JSXAttributeValue::StringLiteral(literal) => {
+                    if let Some(raw) = &literal.raw {
+                        self.handle_string_literal(
+                            &literal.span,
+                            raw,
+                            &attr_name_span,
+                            AttributeContext::StringLiteral,
+                        );
+                    }
+                }
+                JSXAttributeValue::ExpressionContainer(container) => match &container.expression {
+                    JSXExpression::StringLiteral(literal) => {
                         if let Some(raw) = &literal.raw {
                             self.handle_string_literal(
                                 &literal.span,
                                 raw,
-                                &attr_name.span,
-                                AttributeContext::StringLiteral,
+                                &attr_name_span,
+                                AttributeContext::JSXExpression,
                             );
                         }
                     }
-                    JSXAttributeValue::ExpressionContainer(container) => {
-                        if let JSXExpression::StringLiteral(literal) = &container.expression {
-                            if let Some(raw) = &literal.raw {
-                                self.handle_string_literal(
-                                    &literal.span,
-                                    raw,
-                                    &attr_name.span,
-                                    AttributeContext::JSXExpression,
-                                );
-                            }
-                        }
+                    JSXExpression::TemplateLiteral(template) => {
+                        self.handle_template_literal(template, &attr_name_span);
+                        self.attribute_name = outer_attribute_name;
+                        // the whole literal, interpolations included, is already printed
+                        return;
                     }
                     _ => {}
-                }
+                },
+                _ => {}
             }
         }
         walk_jsx_attribute(self, it);
+        self.attribute_name = outer_attribute_name;
     }
 }
@@ -0,0 +1,125 @@
+use dprint_core::formatting::PrintItems;
+use oxc::{
+    allocator::Allocator,
+    ast::ast::Statement,
+    ast_visit::Visit,
+    parser::Parser,
+    span::{SourceType, Span},
+};
+use std::ops::Range;
+
+use crate::{
+    configuration::Configuration,
+    generation::{
+        error::ParseError,
+        generate::create_visitor,
+        markup::{
+            MarkupAttribute, MarkupElement, MarkupTokenizer, MarkupValueKind, MaskedSource,
+            StatementCursor, TextExpressions,
+        },
+        visitor::Visitor,
+    },
+};
+
+pub fn generate(
+    source_text: &str,
+    config: &Configuration,
+    range: Option<&Range<usize>>,
+) -> anyhow::Result<PrintItems> {
+    let elements: Vec<_> = MarkupTokenizer::new(source_text)
+        .with_text_expressions(TextExpressions::Mustache)
+        .collect();
+    let mut visitor = create_visitor(source_text, config, range);
+
+    // script blocks and `:class` bindings are parsed together in one program
+    let mut masked = MaskedSource::new(source_text);
+    let mut lang = None;
+    for element in &elements {
+        if let Some(raw_text) = script_content(source_text, element) {
+            masked.keep(raw_text);
+            lang = lang.or_else(|| script_lang(source_text, element));
+        }
+        for attribute in &element.attributes {
+            if let Some((_, value)) = class_binding_value(source_text, &visitor, attribute) {
+                masked.keep_expression(value);
+            }
+        }
+    }
+
+    let masked_text = masked.into_string();
+    let source_type = SourceType::from_extension(lang.unwrap_or("js"))
+        .map_err(|err| anyhow::anyhow!("Unsupported script lang: {err}"))?;
+    let allocator = Allocator::default();
+    let parsed = Parser::new(&allocator, &masked_text, source_type).parse();
+    if parsed.panicked || !parsed.errors.is_empty() {
+        return Err(ParseError::from_oxc(source_text, &parsed.errors, parsed.panicked).into());
+    }
+
+    let mut statements = StatementCursor::new(&parsed.program);
+    for element in &elements {
+        visitor.enter_markup_element(element);
+
+        for attribute in &element.attributes {
+            match class_binding_value(source_text, &visitor, attribute) {
+                Some((bound_name, value)) => {
+                    for statement in statements.take_within(value) {
+                        if let Statement::ExpressionStatement(statement) = statement {
+                            visitor.visit_class_expression(
+                                &statement.expression,
+                                bound_name,
+                                &attribute.name,
+                            );
+                        }
+                    }
+                }
+                None => visitor.visit_markup_attribute(attribute),
+            }
+        }
+
+        if let Some(raw_text) = script_content(source_text, element) {
+            for statement in statements.take_within(raw_text) {
+                visitor.visit_statement(statement);
+            }
+        }
+    }
+
+    Ok(visitor.finish())
+}
+
+fn script_content(source_text: &str, element: &MarkupElement) -> Option<Span> {
+    element
+        .name
+        .source_text(source_text)
+        .eq_ignore_ascii_case("script")
+        .then_some(element.raw_text)
+        .flatten()
+}
+
+fn script_lang<'s>(source_text: &'s str, element: &MarkupElement) -> Option<&'s str> {
+    element
+        .attributes
+        .iter()
+        .find(|attribute| attribute.name.source_text(source_text) == "lang")
+        .and_then(|attribute| attribute.value.as_ref())
+        .map(|value| value.span.source_text(source_text))
+        .map(|lang| lang.trim_matches(['"', '\'']))
+}
+
+/// Bound name and value span of `:class="..."` or `v-bind:class="..."` when
+/// `class` is configured
+fn class_binding_value<'s>(
+    source_text: &'s str,
+    visitor: &Visitor<'_>,
+    attribute: &MarkupAttribute,
+) -> Option<(&'s str, Span)> {
+    let name = attribute.name.source_text(source_text);
+    let bound_name = name
+        .strip_prefix("v-bind:")
+        .or_else(|| name.strip_prefix(':'))?;
+
+    attribute
+        .value
+        .as_ref()
+        .filter(|value| value.kind == MarkupValueKind::Quoted && visitor.match_attr(bound_name))
+        .map(|value| (bound_name, value.span))
+}
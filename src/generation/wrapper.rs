@@ -1,14 +1,22 @@
 use dprint_core::formatting::{PrintItems, Signal, ir_helpers, utils::string_utils};
 use dprint_core_macros::sc;
+use oxc::span::Span;
+use std::{borrow::Cow, collections::HashMap};
+use unicode_width::UnicodeWidthStr;
 
 use crate::generation::{
+    sorter::sort_classes,
     types::{IntoU32, IntoUsize},
     visitor::{AttributeContext, AttributePos},
 };
 
 #[derive(Debug, Clone, Copy)]
 enum IndentCount {
-    IndentToQuote(u32),
+    /// Column of the quote and the indentation of its line
+    IndentToQuote {
+        column: u32,
+        line_indent: u32,
+    },
     IndentToPre(u32),
 }
 
@@ -16,7 +24,7 @@ impl IndentCount {
     #[inline]
     const fn value(&self) -> u32 {
         match self {
-            Self::IndentToQuote(n) | Self::IndentToPre(n) => *n,
+            Self::IndentToQuote { column: n, .. } | Self::IndentToPre(n) => *n,
         }
     }
 
@@ -26,7 +34,11 @@ impl IndentCount {
     }
 }
 
+#[derive(Debug, Clone, Copy)]
 pub struct WrapperOption {
+    pub use_tabs: bool,
+    pub enable_wrap: bool,
+    pub sort_classes: bool,
     pub allow_line_overflow: bool,
     pub indent_to_quote: bool,
     pub indent_width: u8,
@@ -34,139 +46,358 @@ pub struct WrapperOption {
     pub line_width: u32,
 }
 
+/// Where the previously wrapped value ended, in the source and in the output
+#[derive(Debug, Clone, Copy)]
+struct WrappedEnd {
+    line: u32,
+    source_pos: usize,
+    output_column: u32,
+    /// Indent to previous line of the value, kept by the values following it
+    /// on its last line, which a second pass no longer sees on the same line
+    pre_indent: u32,
+}
+
 pub struct Wrapper {
     option: WrapperOption,
+    attribute_options: HashMap<String, WrapperOption>,
     pre_jsx_element_line: u32,
     pre_indent_count: u32,
+    last_wrapped_end: Option<WrappedEnd>,
 }
 
 impl Wrapper {
     pub fn new(option: WrapperOption) -> Self {
         Self {
             option,
+            attribute_options: HashMap::new(),
             pre_jsx_element_line: 0,
             pre_indent_count: 0,
+            last_wrapped_end: None,
         }
     }
 
+    /// Options of single attributes, taking precedence over the default option
+    #[must_use]
+    pub fn with_attribute_options(mut self, options: HashMap<String, WrapperOption>) -> Self {
+        self.attribute_options = options;
+        self
+    }
+
+    /// Option of the attribute `attr_name`, or the default one
+    pub fn option_for(&self, attr_name: Option<&str>) -> WrapperOption {
+        attr_name
+            .and_then(|name| self.attribute_options.get(name))
+            .copied()
+            .unwrap_or(self.option)
+    }
+
     pub fn set_pre_jsx_element_line(&mut self, source_text: &str, node_span_start: usize) {
         self.pre_jsx_element_line = calculate_line_number(source_text, node_span_start);
     }
 
     pub fn set_pre_indent_count(&mut self, source_text: &str, node_span_start: usize) {
-        self.pre_indent_count = calculate_column_number(source_text, node_span_start)
+        self.pre_indent_count = self.output_column_number(source_text, node_span_start)
     }
 }
 
 impl Wrapper {
     pub fn format(
-        &self,
+        &mut self,
+        option: &WrapperOption,
         node_text: &str,
         attr_pos: AttributePos,
         context: AttributeContext,
     ) -> PrintItems {
-        let indent_count = self.parse_indent(attr_pos);
+        let pre_indent = self.parse_pre_indent(option, attr_pos);
+        let indent_count = if option.indent_to_quote {
+            self.parse_quote_indent(option, attr_pos)
+        } else {
+            IndentCount::IndentToPre(pre_indent)
+        };
 
         let attr_value_column =
-            calculate_column_number(attr_pos.source_text(), attr_pos.attr_value_span_start());
-        let wrapped_items = self.wrap_text(node_text, &indent_count, attr_value_column, context);
-
-        if self.option.indent_to_quote {
+            self.output_column_number(attr_pos.source_text(), attr_pos.attr_value_span_start());
+        let parts = quoted_parts(
+            node_text,
+            attr_pos.attr_value_span_start(),
+            attr_pos.interpolation_spans(),
+            context != AttributeContext::StringLiteral,
+            option.sort_classes,
+        );
+        let (wrapped_items, end_column) =
+            self.wrap_text(option, &parts, &indent_count, attr_value_column, context);
+
+        let source_end = attr_pos.attr_value_span_start() + node_text.len();
+        self.last_wrapped_end = Some(WrappedEnd {
+            line: calculate_line_number(attr_pos.source_text(), source_end),
+            source_pos: source_end,
+            output_column: end_column,
+            pre_indent,
+        });
+
+        if option.indent_to_quote {
             wrapped_items
         } else {
-            ir_helpers::with_indent_times(
-                wrapped_items,
-                indent_count.level(self.option.indent_width),
-            )
+            ir_helpers::with_indent_times(wrapped_items, indent_count.level(option.indent_width))
         }
     }
 }
 
 impl Wrapper {
-    fn parse_indent(&self, attr_pos: AttributePos) -> IndentCount {
-        if self.option.indent_to_quote {
-            return IndentCount::IndentToQuote(calculate_column_number(
-                attr_pos.source_text(),
-                attr_pos.attr_value_span_start(),
-            ));
+    /// Column of `start` once the values wrapped earlier on the same line are printed
+    fn output_column_number(&self, text: &str, start: usize) -> u32 {
+        let column = calculate_column_number(text, start, self.option.indent_width);
+
+        match self.last_wrapped_end {
+            Some(end)
+                if start >= end.source_pos && calculate_line_number(text, start) == end.line =>
+            {
+                let end_column =
+                    calculate_column_number(text, end.source_pos, self.option.indent_width);
+                end.output_column + column.saturating_sub(end_column)
+            }
+            _ => column,
         }
+    }
 
-        let line = calculate_line_number(attr_pos.source_text(), attr_pos.attr_value_span_start());
-        let indent_width = u32::from(self.option.indent_width);
-
-        let indent_count = if line == self.pre_jsx_element_line {
-            self.pre_indent_count + indent_width
-        } else {
-            calculate_column_number(attr_pos.source_text(), attr_pos.attr_name_span_start())
-                + indent_width
-        };
+    fn parse_quote_indent(&self, option: &WrapperOption, attr_pos: AttributePos) -> IndentCount {
+        let start = attr_pos.attr_value_span_start();
+        IndentCount::IndentToQuote {
+            column: self.output_column_number(attr_pos.source_text(), start),
+            line_indent: calculate_line_indent(attr_pos.source_text(), start, option.indent_width),
+        }
+    }
 
-        IndentCount::IndentToPre(indent_count)
+    fn parse_pre_indent(&self, option: &WrapperOption, attr_pos: AttributePos) -> u32 {
+        let text = attr_pos.source_text();
+        let start = attr_pos.attr_value_span_start();
+        let line = calculate_line_number(text, start);
+
+        match self.last_wrapped_end {
+            // same line as the previous value, whose wrapping may have moved it
+            Some(end) if start >= end.source_pos && line == end.line => end.pre_indent,
+            _ if line == self.pre_jsx_element_line => {
+                self.pre_indent_count + u32::from(option.indent_width)
+            }
+            _ => {
+                self.output_column_number(text, attr_pos.attr_name_span_start())
+                    + u32::from(option.indent_width)
+            }
+        }
     }
 
     fn wrap_text(
         &self,
-        node_text: &str,
+        option: &WrapperOption,
+        parts: &[Cow<'_, str>],
         indent_count: &IndentCount,
         first_lint_column: u32,
         context: AttributeContext,
-    ) -> PrintItems {
-        let parts: Vec<_> = node_text
-            .split_whitespace()
-            .filter(|c| *c != "\\")
-            .collect();
-
+    ) -> (PrintItems, u32) {
         if parts.is_empty() {
-            return PrintItems::new();
+            return (PrintItems::new(), first_lint_column);
         }
 
         let last_index = parts.len() - 1;
         let mut current_width = first_lint_column;
+        // actual output column, `current_width` may exclude the indent
+        let mut line_column = first_lint_column;
+        let break_column = match indent_count {
+            IndentCount::IndentToQuote { column, .. } => *column,
+            IndentCount::IndentToPre(_) => {
+                indent_count.level(option.indent_width) * u32::from(option.indent_width)
+            }
+        };
 
-        parts
+        let items = parts
             .iter()
             .enumerate()
             .fold(PrintItems::new(), |mut items, (i, text)| {
-                let text_width = text.chars().count().into_u32();
+                // the first part is glued to the opening quote, and an
+                // overflowing part leaves `current_width` at the line start
+                let leading_space = i > 0 && current_width > 0;
+                let text_width = display_width(text);
                 let next_width = current_width + text_width + 1;
 
-                let exceeds_width = if self.option.line_width_includes_indent {
-                    next_width > self.option.line_width.saturating_sub(indent_count.value())
+                let exceeds_width = if !option.enable_wrap {
+                    false
+                } else if option.line_width_includes_indent {
+                    next_width > option.line_width.saturating_sub(indent_count.value())
                 } else {
-                    next_width > self.option.line_width
+                    next_width > option.line_width
                 };
 
-                match (exceeds_width, self.option.allow_line_overflow) {
+                match (exceeds_width, option.allow_line_overflow) {
                     (true, true) => {
-                        append_text(&mut items, text, current_width > 0);
+                        line_column =
+                            advance_column(line_column, text, leading_space, option.indent_width);
+                        append_text(&mut items, text, leading_space);
                         current_width = 0;
 
                         if i < last_index {
-                            append_break_line(&mut items, context, indent_count);
+                            append_break_line(&mut items, context, indent_count, option);
+                            line_column = break_column;
                         }
                     }
-                    (true, false) => {
-                        append_break_line(&mut items, context, indent_count);
+                    // never break before the first part, it carries the opening quote
+                    (true, false) if i > 0 => {
+                        append_break_line(&mut items, context, indent_count, option);
                         append_text(&mut items, text, false);
                         current_width = text_width;
+                        line_column =
+                            advance_column(break_column, text, false, option.indent_width);
                     }
-                    (false, _) => {
-                        append_text(&mut items, text, i > 0);
+                    _ => {
+                        append_text(&mut items, text, leading_space);
                         current_width = next_width;
+                        line_column =
+                            advance_column(line_column, text, leading_space, option.indent_width);
                     }
                 }
 
                 items
-            })
+            });
+
+        (items, line_column)
     }
 }
 
-/// 0-indexed
+/// Split on whitespace, except inside `interpolation_spans` (absolute
+/// positions), so that `${...}` stays glued to its neighbouring text.
+///
+/// With `continuations`, a JS line continuation (`\` + newline) next to
+/// whitespace separates classes as well. A continuation between two classes
+/// joins them in the string value, so it stays inside the part.
+fn split_parts<'t>(
+    node_text: &'t str,
+    text_start: usize,
+    interpolation_spans: &[Span],
+    continuations: bool,
+) -> Vec<&'t str> {
+    let in_interpolation = |offset: usize| {
+        let pos = text_start + offset;
+        interpolation_spans
+            .iter()
+            .any(|span| span.start as usize <= pos && pos < span.end as usize)
+    };
+
+    let mut parts = Vec::new();
+    let mut part_start = None;
+    let mut in_continuation = false;
+
+    for (offset, c) in node_text.char_indices() {
+        let is_separator = if in_continuation {
+            in_continuation = c == '\r';
+            false
+        } else if continuations && c == '\\' {
+            let (before, after) = (&node_text[..offset], &node_text[offset + 1..]);
+            let line_break = after
+                .strip_prefix("\r\n")
+                .or_else(|| after.strip_prefix('\n'));
+
+            match line_break {
+                Some(next_line) => {
+                    let separates = before.ends_with(char::is_whitespace)
+                        || next_line.is_empty()
+                        || next_line.starts_with(char::is_whitespace);
+                    in_continuation = !separates;
+                    separates
+                }
+                None => false,
+            }
+        } else {
+            c.is_whitespace()
+        };
+
+        if is_separator && !in_interpolation(offset) {
+            if let Some(start) = part_start.take() {
+                parts.push(&node_text[start..offset]);
+            }
+        } else if part_start.is_none() {
+            part_start = Some(offset);
+        }
+    }
+
+    if let Some(start) = part_start {
+        parts.push(&node_text[start..]);
+    }
+
+    parts.retain(|c| *c != "\\");
+    parts
+}
+
+/// Output column after appending `text`
+fn advance_column(column: u32, text: &str, leading_space: bool, tab_width: u8) -> u32 {
+    let column = column + u32::from(leading_space);
+
+    match text.rsplit_once('\n') {
+        Some((_, last_line)) => line_width(last_line, tab_width),
+        None => column + display_width(text),
+    }
+}
+
+/// Split like [`split_parts`] between the quotes, optionally sorting the
+/// classes, then glue the quotes to the first and last class so that
+/// whitespace next to a quote never leaves it on a line of its own.
+fn quoted_parts<'t>(
+    node_text: &'t str,
+    text_start: usize,
+    interpolation_spans: &[Span],
+    continuations: bool,
+    sort: bool,
+) -> Vec<Cow<'t, str>> {
+    let quote = match node_text.chars().next() {
+        Some(c @ ('"' | '\'' | '`')) if node_text.len() > 1 && node_text.ends_with(c) => c,
+        _ => {
+            let mut parts = split_parts(node_text, text_start, interpolation_spans, continuations);
+            if sort {
+                sort_classes(&mut parts);
+            }
+            return parts.into_iter().map(Cow::Borrowed).collect();
+        }
+    };
+
+    let inner = &node_text[1..node_text.len() - 1];
+    let mut parts = split_parts(inner, text_start + 1, interpolation_spans, continuations);
+    if sort {
+        sort_classes(&mut parts);
+    }
+
+    let mut parts: Vec<_> = parts.into_iter().map(Cow::Borrowed).collect();
+    match parts.as_mut_slice() {
+        [] => parts.push(Cow::Owned(format!("{quote}{quote}"))),
+        [only] => *only = Cow::Owned(format!("{quote}{only}{quote}")),
+        [first, .., last] => {
+            *first = Cow::Owned(format!("{quote}{first}"));
+            *last = Cow::Owned(format!("{last}{quote}"));
+        }
+    }
+    parts
+}
+
+/// Columns taken by `text` in a terminal or editor, wide CJK characters and
+/// emoji count as two
 #[inline]
-fn calculate_column_number(text: &str, start: usize) -> u32 {
-    string_utils::get_column_number_of_pos(text, start)
-        .into_u32()
-        .saturating_sub(1)
+fn display_width(text: &str) -> u32 {
+    text.width().into_u32()
+}
+
+/// Display width of a line, tabs advance to the next multiple of `tab_width`
+fn line_width(line: &str, tab_width: u8) -> u32 {
+    let tab_width = u32::from(tab_width);
+    let mut segments = line.split('\t');
+    let first = display_width(segments.next().unwrap_or_default());
+
+    segments.fold(first, |column, segment| {
+        (column / tab_width + 1) * tab_width + display_width(segment)
+    })
+}
+
+/// 0-indexed, in display width
+#[inline]
+fn calculate_column_number(text: &str, start: usize, tab_width: u8) -> u32 {
+    let line_start = text[..start].rfind('\n').map_or(0, |i| i + 1);
+    line_width(&text[line_start..start], tab_width)
 }
 
 /// 0-indexed
@@ -177,6 +408,17 @@ fn calculate_line_number(text: &str, start: usize) -> u32 {
         .saturating_sub(1)
 }
 
+/// Width of the tabs starting the line of `start`. Spaces after them align
+/// rather than indent, like the ones of an earlier wrapped value.
+fn calculate_line_indent(text: &str, start: usize, tab_width: u8) -> u32 {
+    let line_start = text[..start].rfind('\n').map_or(0, |i| i + 1);
+    let tabs = text[line_start..start]
+        .chars()
+        .take_while(|c| *c == '\t')
+        .count();
+    tabs.into_u32() * u32::from(tab_width)
+}
+
 /// 0-indexed
 #[inline]
 fn calculate_indent_level(column: usize, indent_width: u8) -> u32 {
@@ -193,7 +435,15 @@ fn append_text(items: &mut PrintItems, text: &str, leading_space: bool) {
     if leading_space {
         items.push_space();
     }
-    items.push_string(text.to_string());
+
+    if text.contains('\n') {
+        // multi-line interpolation, keep it verbatim
+        items.push_signal(Signal::StartIgnoringIndent);
+        items.extend(ir_helpers::gen_from_string(text));
+        items.push_signal(Signal::FinishIgnoringIndent);
+    } else {
+        items.push_string(text.to_string());
+    }
 }
 
 // fn push_jsxexpression_endl(items: &mut PrintItems, jsxexpression: bool) {
@@ -211,6 +461,7 @@ fn append_break_line(
     items: &mut PrintItems,
     context: AttributeContext,
     indent_count: &IndentCount,
+    option: &WrapperOption,
 ) {
     if matches!(context, AttributeContext::JSXExpression) {
         items.push_space();
@@ -219,7 +470,19 @@ fn append_break_line(
 
     items.push_signal(Signal::NewLine);
 
-    if let IndentCount::IndentToQuote(column) = indent_count {
-        append_spaces(items, *column);
+    if let IndentCount::IndentToQuote {
+        column,
+        line_indent,
+    } = indent_count
+    {
+        // tabs for the indentation of the line, spaces for the alignment to
+        // the quote, so that it holds with any tab width
+        let tabs = if option.use_tabs {
+            calculate_indent_level(line_indent.into_usize(), option.indent_width)
+        } else {
+            0
+        };
+        (0..tabs).for_each(|_| items.push_signal(Signal::Tab));
+        append_spaces(items, *column - tabs * u32::from(option.indent_width));
     }
 }
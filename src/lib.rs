@@ -4,6 +4,7 @@ mod generation;
 
 pub use format_text::FormatTextOptions;
 pub use format_text::format_text;
+pub use generation::{ParseDiagnostic, ParseError};
 
 #[cfg(feature = "wasm")]
 #[cfg(target_arch = "wasm32")]
@@ -1,55 +1,308 @@
-use std::path::Path;
-
-use dprint_plugin_classname_wrap::{FormatTextOptions, configuration, format_text};
-
-fn main() -> anyhow::Result<()> {
-    let source_text = r#"import React from 'react';
-
-/**
- * A simple counter component
- */
-export const Counter: React.FC = () => {
-    const [count, setCount] = React.useState(0);
-    const tempStr = `this is a ${count}, don't split by \n, plz`;
-
-    return (
-        <div class='p-2 m-3 w-full hover:bg-red *:text-sm'>
-            <p class='text-blue'>Count: {count}</p>
-            <div class='m-auto grid min-h-screen select-none grid-rows-[auto_1fr_auto] place-items-center overflow-x-hidden bg-ctp-base text-ctp-text scrollbar-thin scrollbar-thumb-ctp-surface0 hover:scrollbar-thumb-ctp-surface2 md:w-[calc(768px+100vw-100%)] md:pl-[calc(100vw-100%)]'>DIV</div>
-            <button
-                class='m-auto grid min-h-screen select-none grid-rows-[auto_1fr_auto] place-items-center overflow-x-hidden bg-ctp-base text-ctp-text scrollbar-thin scrollbar-thumb-ctp-surface0 hover:scrollbar-thumb-ctp-surface2 md:w-[calc(768px+100vw-100%)] md:pl-[calc(100vw-100%)]'
-                onClick={() => setCount(count + 1)}
-            >
-                Increment
-            </button>
-            <button class={'w-full h-full place-content-center-safe *:[&_img,&_svg]:w-1/4 *:[&_img,&_svg]:m-auto'} onClick={() => setCount(count - 1)}>Decrement</button>
-            <button className={'m-auto grid min-h-screen select-none grid-rows-[auto_1fr_auto] place-items-center overflow-x-hidden bg-ctp-base text-ctp-text scrollbar-thin scrollbar-thumb-ctp-surface0 hover:scrollbar-thumb-ctp-surface2 md:w-[calc(768px+100vw-100%)] md:pl-[calc(100vw-100%)]'} onClick={() => setCount(count - 1)}>Decrement</button>
-            <article class="prose max-w-none select-text pt-4 text-ctp-text dark:prose-invert selection:bg-ctp-lavender selection:text-ctp-crust prose-headings:text-ctp-text prose-h2:mt-14 prose-h2:text-ctp-lavender prose-h3:mb-5 prose-h3:mt-12 prose-h3:text-ctp-subtext1 prose-h4:mb-4 prose-h4:mt-10 prose-h4:w-fit prose-h4:border-l-8 prose-h4:border-ctp-lavender prose-h4:bg-ctp-surface0 prose-h4:px-3 prose-h4:py-1 prose-h4:text-ctp-subtext0 prose-a:text-ctp-lavender prose-a:no-underline prose-blockquote:border-ctp-surface0 prose-blockquote:not-italic prose-blockquote:text-ctp-overlay1 prose-strong:font-bold prose-strong:text-ctp-teal prose-em:italic prose-em:text-ctp-sky prose-kbd:select-none prose-kbd:border prose-kbd:border-b-4 prose-kbd:border-ctp-surface2 prose-kbd:text-ctp-subtext1 prose-kbd:shadow-none prose-pre:rounded prose-pre:border-4 prose-pre:border-ctp-crust prose-pre:bg-ctp-crust prose-pre:text-ctp-text prose-pre:scrollbar-thin prose-pre:scrollbar-thumb-ctp-surface0 prose-th:text-ctp-lavender prose-img:mx-auto prose-img:rounded prose-img:shadow-md prose-img:shadow-ctp-crust prose-hr:border-ctp-surface2 hover:prose-a:underline hover:prose-kbd:border-ctp-lavender hover:prose-kbd:text-ctp-lavender hover:prose-pre:scrollbar-thumb-ctp-surface2 prose-inline-code:break-words prose-inline-code:rounded prose-inline-code:bg-ctp-surface0 prose-inline-code:px-2 prose-inline-code:py-1 prose-inline-code:text-ctp-subtext1 prose-inline-code:before:content-[''] prose-inline-code:after:content-[''] md:prose-hr:mx-12">
-                {markdown}
-            </article>
-        </div>
-    )
-}"#;
-
-    match format_text(FormatTextOptions {
-        path: Path::new("Counter.tsx"),
+use std::{
+    io::{Read, Write},
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use anyhow::{Context, bail};
+use dprint_core::configuration::{ConfigKeyMap, resolve_global_config};
+use dprint_plugin_classname_wrap::{
+    FormatTextOptions,
+    configuration::{self, Configuration},
+    format_text,
+};
+
+const USAGE: &str = "\
+Usage: dprint-plugin-classname-wrap [OPTIONS] [FILES]...
+
+Formats the given files, or stdin when no file is given, and prints the result.
+
+Options:
+      --config <FILE>          JSON configuration, with the keys of the dprint plugin
+      --write                  Write the formatted files in place
+      --check                  List the files that would change and exit with 1 if any
+      --stdin-filepath <PATH>  Path of the text read from stdin, selects the file type
+      --schema                 Print the JSON schema of the configuration
+  -h, --help                   Print help
+
+Exits with 2 when a file or the configuration cannot be read or formatted.";
+
+/// Exit status of `--check` when a file would change
+const CHANGED: u8 = 1;
+/// Exit status of an error, taking precedence over `CHANGED`
+const ERROR: u8 = 2;
+
+#[derive(Default)]
+struct Args {
+    config: Option<PathBuf>,
+    write: bool,
+    check: bool,
+    schema: bool,
+    stdin_filepath: Option<PathBuf>,
+    files: Vec<PathBuf>,
+}
+
+impl Args {
+    fn parse(args: impl Iterator<Item = String>) -> anyhow::Result<Option<Self>> {
+        let mut parsed = Self::default();
+        let mut args = args.peekable();
+
+        while let Some(arg) = args.next() {
+            let (name, inline_value) = match arg.split_once('=') {
+                Some((name, value)) if name.starts_with("--") => (name, Some(value.to_string())),
+                _ => (arg.as_str(), None),
+            };
+            let mut value = || {
+                inline_value
+                    .clone()
+                    .or_else(|| args.next())
+                    .with_context(|| format!("Missing value for {name}"))
+            };
+
+            match name {
+                "-h" | "--help" => return Ok(None),
+                "--config" => parsed.config = Some(value()?.into()),
+                "--stdin-filepath" => parsed.stdin_filepath = Some(value()?.into()),
+                "--write" => parsed.write = true,
+                "--check" => parsed.check = true,
+                "--schema" => parsed.schema = true,
+                "--" => parsed.files.extend(args.by_ref().map(PathBuf::from)),
+                _ if name.starts_with('-') && name != "-" => bail!("Unknown option {name}"),
+                _ => parsed.files.push(arg.into()),
+            }
+        }
+
+        if parsed.write && parsed.check {
+            bail!("--write and --check cannot be used together");
+        }
+
+        Ok(Some(parsed))
+    }
+}
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(code) => code,
+        Err(err) => {
+            eprintln!("error: {err:#}");
+            ExitCode::from(ERROR)
+        }
+    }
+}
+
+fn run() -> anyhow::Result<ExitCode> {
+    let Some(args) = Args::parse(std::env::args().skip(1))? else {
+        println!("{USAGE}");
+        return Ok(ExitCode::SUCCESS);
+    };
+    if args.schema {
+        println!("{:#}", configuration::json_schema());
+        return Ok(ExitCode::SUCCESS);
+    }
+    let config = load_config(args.config.as_deref())?;
+
+    if args.files.is_empty() {
+        return format_stdin(&args, &config);
+    }
+
+    Ok(ExitCode::from(format_files(&args, &config)))
+}
+
+/// Formats every file, reporting the errors of single files without stopping.
+/// Returns the exit status.
+fn format_files(args: &Args, config: &Configuration) -> u8 {
+    let mut status = 0;
+    for path in &args.files {
+        match format_file(args, path, config) {
+            Ok(true) if args.check => status = status.max(CHANGED),
+            Ok(_) => {}
+            Err(err) => {
+                eprintln!("error: {}: {err:#}", path.display());
+                status = ERROR;
+            }
+        }
+    }
+    status
+}
+
+/// Resolve the JSON file like dprint does, global keys such as `lineWidth`
+/// are used as defaults
+fn load_config(path: Option<&Path>) -> anyhow::Result<Configuration> {
+    let mut config_map = match path {
+        Some(path) => {
+            let text = std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            serde_json::from_str::<ConfigKeyMap>(&text)
+                .with_context(|| format!("Failed to parse {}", path.display()))?
+        }
+        None => ConfigKeyMap::new(),
+    };
+
+    let global = resolve_global_config(&mut config_map);
+    let resolved = Configuration::resolve_config(config_map, &global.config);
+    let diagnostics: Vec<_> = global
+        .diagnostics
+        .into_iter()
+        .chain(resolved.diagnostics)
+        .map(|diagnostic| format!("\n  {diagnostic}"))
+        .collect();
+    if !diagnostics.is_empty() {
+        bail!("Invalid configuration:{}", diagnostics.concat());
+    }
+
+    Ok(resolved.config)
+}
+
+fn format(path: &Path, text: String, config: &Configuration) -> anyhow::Result<Option<String>> {
+    format_text(FormatTextOptions {
+        path,
         extension: None,
-        text: source_text.to_string(),
-        config: &configuration::Configuration::default()
-            .with_classname_attributes(
-                vec!["class".to_string(), "className".to_string()]
-                    .into_iter()
-                    .collect(),
-            )
-            .with_indent_to_quote(true)
-            .with_indent_width(4)
-            .with_line_width(80)
-            .with_allow_line_overflow(false)
-            .with_line_width_includes_indent(false),
-    })? {
-        Some(text) => println!("{text}"),
-        None => todo!(),
-    }
-
-    Ok(())
+        text,
+        config,
+        range: None,
+    })
+}
+
+/// Returns whether the file changed
+fn format_file(args: &Args, path: &Path, config: &Configuration) -> anyhow::Result<bool> {
+    let text = std::fs::read_to_string(path).context("Failed to read file")?;
+    let formatted = format(path, text.clone(), config)?;
+
+    if args.check {
+        if formatted.is_some() {
+            println!("{}", path.display());
+        }
+    } else if args.write {
+        if let Some(formatted) = &formatted {
+            std::fs::write(path, formatted).context("Failed to write file")?;
+        }
+    } else {
+        print!("{}", formatted.as_deref().unwrap_or(&text));
+    }
+
+    Ok(formatted.is_some())
+}
+
+fn format_stdin(args: &Args, config: &Configuration) -> anyhow::Result<ExitCode> {
+    if args.write {
+        bail!("--write needs file paths");
+    }
+
+    let path = args
+        .stdin_filepath
+        .as_deref()
+        .unwrap_or(Path::new("stdin.tsx"));
+    let mut text = String::new();
+    std::io::stdin()
+        .read_to_string(&mut text)
+        .context("Failed to read stdin")?;
+    let formatted = format(path, text.clone(), config)?;
+
+    if args.check {
+        return Ok(ExitCode::from(if formatted.is_some() {
+            CHANGED
+        } else {
+            0
+        }));
+    }
+
+    std::io::stdout().write_all(formatted.as_deref().unwrap_or(&text).as_bytes())?;
+    Ok(ExitCode::SUCCESS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> anyhow::Result<Option<Args>> {
+        Args::parse(args.iter().map(ToString::to_string))
+    }
+
+    fn parse_ok(args: &[&str]) -> Args {
+        parse(args).unwrap().unwrap()
+    }
+
+    /// Directory with `files`, removed on drop
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str, files: &[(&str, &str)]) -> Self {
+            let dir =
+                std::env::temp_dir().join(format!("classname-wrap-{name}-{}", std::process::id()));
+            std::fs::create_dir_all(&dir).unwrap();
+            for (file, text) in files {
+                std::fs::write(dir.join(file), text).unwrap();
+            }
+            Self(dir)
+        }
+
+        fn check(&self, files: &[&str]) -> u8 {
+            let args = Args {
+                check: true,
+                files: files.iter().map(|file| self.0.join(file)).collect(),
+                ..Args::default()
+            };
+            format_files(&args, &Configuration::default().with_line_width(20))
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn parses_options_with_inline_and_separate_values() {
+        let args = parse_ok(&["--config=a.json", "--stdin-filepath", "b.tsx", "--check"]);
+        assert_eq!(args.config, Some(PathBuf::from("a.json")));
+        assert_eq!(args.stdin_filepath, Some(PathBuf::from("b.tsx")));
+        assert!(args.check && !args.write && !args.schema);
+
+        // only the first `=` separates the value
+        let args = parse_ok(&["--config=a=b.json"]);
+        assert_eq!(args.config, Some(PathBuf::from("a=b.json")));
+
+        assert!(parse(&["--config"]).is_err());
+        assert!(parse(&["--help", "--unknown"]).unwrap().is_none());
+    }
+
+    #[test]
+    fn parses_files_after_double_dash_and_dash() {
+        let args = parse_ok(&["a.tsx", "-", "--", "--write", "-x"]);
+        assert_eq!(
+            args.files,
+            ["a.tsx", "-", "--write", "-x"].map(PathBuf::from)
+        );
+        assert!(!args.write);
+    }
+
+    #[test]
+    fn rejects_unknown_options_and_write_with_check() {
+        let err = parse(&["--unknown"]).err().unwrap();
+        assert_eq!(err.to_string(), "Unknown option --unknown");
+        assert!(parse(&["-x=1"]).is_err());
+        assert!(parse(&["--write", "--check"]).is_err());
+    }
+
+    #[test]
+    fn check_exit_status() {
+        let dir = TempDir::new(
+            "check",
+            &[
+                ("formatted.tsx", "<b className=\"p-2\" />;\n"),
+                (
+                    "unformatted.tsx",
+                    "<div className=\"px-2 py-4 text-sm font-bold\" />;\n",
+                ),
+                ("unsupported.md", "className"),
+            ],
+        );
+
+        assert_eq!(dir.check(&["formatted.tsx"]), 0);
+        assert_eq!(dir.check(&["formatted.tsx", "unformatted.tsx"]), CHANGED);
+        // read and format errors win over changed files
+        assert_eq!(dir.check(&["unformatted.tsx", "missing.tsx"]), ERROR);
+        assert_eq!(dir.check(&["unsupported.md", "unformatted.tsx"]), ERROR);
+    }
 }
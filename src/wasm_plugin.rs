@@ -2,7 +2,7 @@ use dprint_core::configuration::*;
 use dprint_core::generate_plugin_code;
 use dprint_core::plugins::*;
 
-use super::configuration::Configuration;
+use super::configuration::{Configuration, check_config_updates};
 use super::format_text::{FormatTextOptions, format_text};
 
 struct ClassnameWrapPluginHandler;
@@ -26,6 +26,9 @@ impl SyncPluginHandler<Configuration> for ClassnameWrapPluginHandler {
                     String::from("tsx"),
                     String::from("jsx"),
                     String::from("html"),
+                    String::from("vue"),
+                    String::from("svelte"),
+                    String::from("astro"),
                 ],
                 file_names: Vec::new(),
             },
@@ -39,9 +42,15 @@ impl SyncPluginHandler<Configuration> for ClassnameWrapPluginHandler {
             name,
             version: version.clone(),
             config_key: "classnameWrap".to_string(),
-            help_url: "".to_string(),
-            config_schema_url: "".to_string(),
-            update_url: Some("https://plugins.dprint.dev/liuhq/dprint-plugin-classname-wrap/latest.json".to_string()),
+            help_url: "https://github.com/liuhq/dprint-plugin-classname-wrap#configuration"
+                .to_string(),
+            config_schema_url: format!(
+                "https://plugins.dprint.dev/liuhq/dprint-plugin-classname-wrap/{version}/schema.json"
+            ),
+            update_url: Some(
+                "https://plugins.dprint.dev/liuhq/dprint-plugin-classname-wrap/latest.json"
+                    .to_string(),
+            ),
         }
     }
 
@@ -53,9 +62,9 @@ impl SyncPluginHandler<Configuration> for ClassnameWrapPluginHandler {
 
     fn check_config_updates(
         &self,
-        _message: CheckConfigUpdatesMessage,
+        message: CheckConfigUpdatesMessage,
     ) -> anyhow::Result<Vec<ConfigChange>> {
-        Ok(Vec::new())
+        Ok(check_config_updates(&message.config))
     }
 
     fn format(
@@ -69,6 +78,7 @@ impl SyncPluginHandler<Configuration> for ClassnameWrapPluginHandler {
             extension: None,
             text: file_text,
             config: request.config,
+            range: request.range,
         })
         .map(|maybe_text| maybe_text.map(|t| t.into_bytes()))
     }
@@ -0,0 +1,108 @@
+use std::collections::BTreeSet;
+
+use dprint_core::configuration::*;
+use dprint_plugin_classname_wrap::configuration::{AttributeOverrides, Configuration, json_schema};
+use serde_json::{Value, json};
+
+fn property_names(properties: &Value) -> BTreeSet<String> {
+    properties.as_object().unwrap().keys().cloned().collect()
+}
+
+fn serialized_names(value: impl serde::Serialize) -> BTreeSet<String> {
+    property_names(&serde_json::to_value(value).unwrap())
+}
+
+/// Serialized configuration, lists built from sets are sorted to compare them
+fn normalized(config: &Configuration) -> serde_json::Map<String, Value> {
+    let mut value = serde_json::to_value(config).unwrap();
+    for (_, property) in value.as_object_mut().unwrap().iter_mut() {
+        if let Value::Array(items) = property {
+            items.sort_by_key(ToString::to_string);
+        }
+    }
+    value.as_object().unwrap().clone()
+}
+
+fn resolved_defaults() -> Configuration {
+    Configuration::resolve_config(ConfigKeyMap::new(), &GlobalConfiguration::default()).config
+}
+
+/// Default value of a schema property, following a `$ref` into `definitions`
+fn default_of(schema: &Value, property: &Value) -> Value {
+    match property["$ref"].as_str() {
+        Some(reference) => {
+            let name = reference.trim_start_matches("#/definitions/");
+            schema["definitions"][name]["default"].clone()
+        }
+        None => property["default"].clone(),
+    }
+}
+
+#[test]
+fn schema_lists_every_option() {
+    let schema = json_schema();
+
+    // `attributeOverrides` is resolved from the object entries of `classnameAttributes`
+    let mut options = serialized_names(Configuration::default());
+    options.remove("attributeOverrides");
+    assert_eq!(property_names(&schema["properties"]), options);
+
+    let entry = &schema["properties"]["classnameAttributes"]["items"]["oneOf"][1];
+    let mut entry_options = serialized_names(AttributeOverrides::default());
+    entry_options.insert(String::from("name"));
+    assert_eq!(property_names(&entry["properties"]), entry_options);
+}
+
+#[test]
+fn resolve_config_accepts_schema_defaults() {
+    let schema = json_schema();
+    let mut config = serde_json::Map::new();
+    for (name, property) in schema["properties"].as_object().unwrap() {
+        config.insert(name.clone(), default_of(&schema, property));
+    }
+
+    let entry_schema = &schema["properties"]["classnameAttributes"]["items"]["oneOf"][1];
+    let mut entry = serde_json::Map::new();
+    for (name, property) in entry_schema["properties"].as_object().unwrap() {
+        entry.insert(name.clone(), default_of(&schema, property));
+    }
+    entry.insert(String::from("name"), json!("tw"));
+    config["classnameAttributes"]
+        .as_array_mut()
+        .unwrap()
+        .push(Value::Object(entry));
+
+    let config: ConfigKeyMap = serde_json::from_value(Value::Object(config)).unwrap();
+    let result = Configuration::resolve_config(config, &GlobalConfiguration::default());
+    assert!(
+        result.diagnostics.is_empty(),
+        "{:?}",
+        result
+            .diagnostics
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn schema_defaults_match_resolve_config() {
+    let schema = json_schema();
+    let resolved = normalized(&resolved_defaults());
+
+    for (name, property) in schema["properties"].as_object().unwrap() {
+        let mut default = default_of(&schema, property);
+        if let Value::Array(items) = &mut default {
+            items.sort_by_key(ToString::to_string);
+        }
+        assert_eq!(default, resolved[name], "default of `{name}`");
+    }
+}
+
+#[test]
+fn default_matches_resolve_config() {
+    assert_eq!(
+        normalized(&Configuration::default()),
+        normalized(&resolved_defaults())
+    );
+}
@@ -1 +1,37 @@
-fn main() {}
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use dprint_core::configuration::*;
+use dprint_development::*;
+use dprint_plugin_classname_wrap::configuration::Configuration;
+use dprint_plugin_classname_wrap::{FormatTextOptions, format_text};
+
+fn main() {
+    let global_config = GlobalConfiguration::default();
+
+    run_specs(
+        &PathBuf::from("./tests/specs"),
+        &ParseSpecOptions {
+            default_file_name: "file.tsx",
+        },
+        &RunSpecsOptions {
+            fix_failures: false,
+            format_twice: true,
+        },
+        Arc::new(move |path, file_text, spec_config| {
+            let spec_config: ConfigKeyMap =
+                serde_json::from_value(spec_config.clone().into()).unwrap();
+            let config_result = Configuration::resolve_config(spec_config, &global_config);
+            ensure_no_diagnostics(&config_result.diagnostics);
+
+            format_text(FormatTextOptions {
+                path,
+                extension: None,
+                text: file_text.to_string(),
+                config: &config_result.config,
+                range: None,
+            })
+        }),
+        Arc::new(move |_, _, _| panic!("Plugin does not support dprint-core tracing.")),
+    )
+}